@@ -0,0 +1,107 @@
+use core::{fmt, ops};
+
+use crate::{ArrayVec, LenType};
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::Align16 {}
+    impl Sealed for super::Align32 {}
+    impl Sealed for super::Align64 {}
+}
+
+/// Marker for the alignment of an [`AlignedArrayVec`]'s storage,
+/// implemented for [`Align16`], [`Align32`] and [`Align64`], this trait is sealed
+pub trait Alignment: sealed::Sealed {}
+
+/// Aligns storage to 16 bytes
+#[repr(align(16))]
+#[derive(Debug, Clone, Copy)]
+pub struct Align16;
+
+/// Aligns storage to 32 bytes
+#[repr(align(32))]
+#[derive(Debug, Clone, Copy)]
+pub struct Align32;
+
+/// Aligns storage to 64 bytes
+#[repr(align(64))]
+#[derive(Debug, Clone, Copy)]
+pub struct Align64;
+
+impl Alignment for Align16 {}
+impl Alignment for Align32 {}
+impl Alignment for Align64 {}
+
+/// [`ArrayVec`] whose first element is aligned to at least the alignment of `A`,
+/// for SIMD loads and DMA buffers that need more than `T`'s natural alignment
+///
+/// Derefs to the inner `ArrayVec` so has all the same methods
+#[repr(C)]
+pub struct AlignedArrayVec<T, const C: usize, A: Alignment, L: LenType = usize> {
+    // zero sized but raises the struct's alignment, the data is at offset
+    // 0 of both this and the ArrayVec as they're repr(C)
+    _align: [A; 0],
+    inner: ArrayVec<T, C, L>,
+}
+
+impl<T, const C: usize, A: Alignment, L: LenType> AlignedArrayVec<T, C, A, L> {
+    /// An empty `AlignedArrayVec`, for initialising `static` and `const` items
+    pub const NEW: Self = Self::new();
+
+    /// Creates a new empty `AlignedArrayVec`
+    /// # Panics
+    /// At compile time if `C` doesn't fit in `L`
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            _align: [],
+            inner: ArrayVec::new(),
+        }
+    }
+
+    /// Moves the elements into an `AlignedArrayVec`
+    pub const fn from_inner(inner: ArrayVec<T, C, L>) -> Self {
+        Self { _align: [], inner }
+    }
+
+    /// Gives back the `ArrayVec`, which is no longer guaranteed to be over-aligned once moved
+    pub fn into_inner(self) -> ArrayVec<T, C, L> {
+        self.inner
+    }
+}
+
+impl<T, const C: usize, A: Alignment, L: LenType> ops::Deref for AlignedArrayVec<T, C, A, L> {
+    type Target = ArrayVec<T, C, L>;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T, const C: usize, A: Alignment, L: LenType> ops::DerefMut for AlignedArrayVec<T, C, A, L> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<T, const C: usize, A: Alignment, L: LenType> fmt::Debug for AlignedArrayVec<T, C, A, L>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<T, const C: usize, A: Alignment, L: LenType> Default for AlignedArrayVec<T, C, A, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const C: usize, A: Alignment, L: LenType> From<ArrayVec<T, C, L>>
+    for AlignedArrayVec<T, C, A, L>
+{
+    fn from(inner: ArrayVec<T, C, L>) -> Self {
+        Self::from_inner(inner)
+    }
+}
@@ -0,0 +1,267 @@
+//! Lock free byte FIFO handing out contiguous regions, see [`ByteQueue`]
+//!
+//! Only available on targets with pointer sized atomics, the finer
+//! `target_has_atomic_load_store` cfg isn't stable so targets with atomic
+//! loads and stores but no compare and swap miss out too
+
+use core::{
+    cell::UnsafeCell,
+    fmt, ops, slice,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::CapacityError;
+
+/// Single producer single consumer byte FIFO of capacity `C` that hands
+/// out contiguous regions for both writing and reading, like a bip buffer
+///
+/// [`ByteQueue::split`] gives a [`Producer`] and a [`Consumer`] which can be
+/// used from different threads or an interrupt handler. The producer asks
+/// for a [`WriteGrant`] of `n` contiguous bytes, fills it (e.g. by DMA) then
+/// commits however many were used. The consumer gets a [`ReadGrant`] over
+/// the oldest contiguous bytes and releases however many were consumed, both
+/// can hold a grant at the same time. When a grant doesn't fit at the end it
+/// wraps to the start, leaving the unused tail to be skipped by the consumer.
+pub struct ByteQueue<const C: usize> {
+    buf: UnsafeCell<[u8; C]>,
+    // next byte to read, only stored by the consumer
+    read: AtomicUsize,
+    // end of the committed bytes, below read once writes have wrapped,
+    // only stored by the producer
+    write: AtomicUsize,
+    // end of the committed bytes before the wrap, only meaningful once
+    // wrapped, only stored by the producer
+    last: AtomicUsize,
+}
+
+// SAFETY: the producer only touches bytes outside read..write and the
+// consumer only bytes inside it, the indices are atomics
+unsafe impl<const C: usize> Sync for ByteQueue<C> {}
+
+impl<const C: usize> ByteQueue<C> {
+    /// Creates a new empty `ByteQueue`
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buf: UnsafeCell::new([0; C]),
+            read: AtomicUsize::new(0),
+            write: AtomicUsize::new(0),
+            last: AtomicUsize::new(0),
+        }
+    }
+
+    /// The maximum number of bytes the queue can store
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub const fn capacity(&self) -> usize {
+        C
+    }
+
+    /// Splits into the writing and reading halves
+    pub fn split(&mut self) -> (Producer<'_, C>, Consumer<'_, C>) {
+        let queue = &*self;
+        (Producer { queue }, Consumer { queue })
+    }
+
+    /// Discards all committed bytes
+    pub fn clear(&mut self) {
+        *self.read.get_mut() = 0;
+        *self.write.get_mut() = 0;
+        *self.last.get_mut() = 0;
+    }
+
+    /// Pointer to the `len` bytes starting at `start`
+    fn region(&self, start: usize, len: usize) -> *mut u8 {
+        debug_assert!(start + len <= C);
+        // SAFETY: start + len <= C
+        unsafe { self.buf.get().cast::<u8>().add(start) }
+    }
+}
+
+impl<const C: usize> Default for ByteQueue<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const C: usize> fmt::Debug for ByteQueue<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ByteQueue").field("capacity", &C).finish()
+    }
+}
+
+/// Writing half of a [`ByteQueue`]
+pub struct Producer<'a, const C: usize> {
+    queue: &'a ByteQueue<C>,
+}
+
+impl<const C: usize> Producer<'_, C> {
+    /// Reserves `n` contiguous bytes to write into
+    /// # Errors
+    /// If there's no contiguous free region of `n` bytes
+    pub fn grant(&mut self, n: usize) -> Result<WriteGrant<'_, C>, CapacityError> {
+        let queue = self.queue;
+        let write = queue.write.load(Ordering::Relaxed);
+        // acquire so the consumer is done with anything it released
+        let read = queue.read.load(Ordering::Acquire);
+        let start = if write < read {
+            // wrapped, one byte is kept free so full and empty differ
+            if write + n >= read {
+                return Err(CapacityError::new(()));
+            }
+            write
+        } else if write + n <= C {
+            write
+        } else if n < read {
+            0
+        } else {
+            return Err(CapacityError::new(()));
+        };
+        Ok(WriteGrant {
+            queue,
+            start,
+            len: n,
+        })
+    }
+}
+
+impl<const C: usize> fmt::Debug for Producer<'_, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Producer").finish_non_exhaustive()
+    }
+}
+
+/// Reading half of a [`ByteQueue`]
+pub struct Consumer<'a, const C: usize> {
+    queue: &'a ByteQueue<C>,
+}
+
+impl<const C: usize> Consumer<'_, C> {
+    /// The oldest contiguous committed bytes, empty if there are none
+    pub fn read(&mut self) -> ReadGrant<'_, C> {
+        let queue = self.queue;
+        // acquire so the committed bytes are visible, last is stored before write
+        let write = queue.write.load(Ordering::Acquire);
+        let last = queue.last.load(Ordering::Acquire);
+        let mut read = queue.read.load(Ordering::Relaxed);
+        if write < read && read == last {
+            // everything before the wrap has been read
+            read = 0;
+            queue.read.store(0, Ordering::Release);
+        }
+        let end = if write < read { last } else { write };
+        ReadGrant {
+            queue,
+            start: read,
+            len: end - read,
+        }
+    }
+
+    /// The number of committed bytes waiting to be read
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let queue = self.queue;
+        let write = queue.write.load(Ordering::Acquire);
+        let last = queue.last.load(Ordering::Acquire);
+        let read = queue.read.load(Ordering::Relaxed);
+        if write < read {
+            last - read + write
+        } else {
+            write - read
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<const C: usize> fmt::Debug for Consumer<'_, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Consumer")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Contiguous region of a [`ByteQueue`] to write into,
+/// nothing is committed if dropped without calling [`WriteGrant::commit`]
+pub struct WriteGrant<'a, const C: usize> {
+    queue: &'a ByteQueue<C>,
+    start: usize,
+    len: usize,
+}
+
+impl<const C: usize> WriteGrant<'_, C> {
+    /// Makes the first `used` bytes of the grant readable
+    /// # Panics
+    /// If `used` is longer than the grant
+    pub fn commit(self, used: usize) {
+        assert!(
+            used <= self.len,
+            "committed {used} bytes but the grant is {}",
+            self.len
+        );
+        let queue = self.queue;
+        let write = queue.write.load(Ordering::Relaxed);
+        let last = queue.last.load(Ordering::Relaxed);
+        let new_write = self.start + used;
+        if self.start < write && write != C {
+            // wrapped, the consumer has to skip from write to the end
+            queue.last.store(write, Ordering::Release);
+        } else if new_write > last {
+            // not wrapped, everything up to the end is readable
+            queue.last.store(C, Ordering::Release);
+        }
+        queue.write.store(new_write, Ordering::Release);
+    }
+}
+
+impl<const C: usize> ops::Deref for WriteGrant<'_, C> {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: the region is free so the consumer won't touch it,
+        // and this grant borrows the producer so there's only one
+        unsafe { slice::from_raw_parts(self.queue.region(self.start, self.len), self.len) }
+    }
+}
+
+impl<const C: usize> ops::DerefMut for WriteGrant<'_, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: see deref
+        unsafe { slice::from_raw_parts_mut(self.queue.region(self.start, self.len), self.len) }
+    }
+}
+
+/// Contiguous committed bytes of a [`ByteQueue`],
+/// nothing is consumed if dropped without calling [`ReadGrant::release`]
+pub struct ReadGrant<'a, const C: usize> {
+    queue: &'a ByteQueue<C>,
+    start: usize,
+    len: usize,
+}
+
+impl<const C: usize> ReadGrant<'_, C> {
+    /// Consumes the first `used` bytes of the grant
+    /// # Panics
+    /// If `used` is longer than the grant
+    pub fn release(self, used: usize) {
+        assert!(
+            used <= self.len,
+            "released {used} bytes but the grant is {}",
+            self.len
+        );
+        // release so the producer only reuses the bytes once they're read
+        self.queue.read.store(self.start + used, Ordering::Release);
+    }
+}
+
+impl<const C: usize> ops::Deref for ReadGrant<'_, C> {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: the region is committed so the producer won't touch it
+        // until it's released
+        unsafe { slice::from_raw_parts(self.queue.region(self.start, self.len), self.len) }
+    }
+}
@@ -0,0 +1,268 @@
+//! Allocation free hex and base64 for byte vectors
+//!
+//! Decoding is done by [`ArrayVec::from_hex`] and [`ArrayVec::from_base64`],
+//! encoding writes ASCII into another byte vector through
+//! [`ArrayVec::encode_hex_into`] and [`ArrayVec::encode_base64_into`]
+//!
+//! Vectors of [`FixedBytes`] numbers round trip through raw bytes of a given
+//! endianness with [`ArrayVec::to_le_bytes_into`] and [`ArrayVec::from_le_bytes`]
+//! and their big endian counterparts
+
+use core::{fmt, iter};
+
+use crate::{ArrayVec, CapacityError, LenType};
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Error decoding hex or base64 text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// A character that isn't part of the encoding, at this byte offset
+    InvalidCharacter(usize),
+    /// The text can't be a whole number of encoded bytes
+    InvalidLength,
+    /// The decoded bytes don't fit in the vector
+    Capacity(CapacityError),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidCharacter(at) => write!(f, "invalid character at offset {at}"),
+            Self::InvalidLength => f.write_str("invalid length"),
+            Self::Capacity(err) => err.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+impl From<CapacityError> for DecodeError {
+    fn from(err: CapacityError) -> Self {
+        Self::Capacity(err)
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Number with a fixed size byte representation, implemented for the
+/// primitive integers and floats, this trait is sealed
+pub trait FixedBytes: Copy + sealed::Sealed {
+    /// The number of bytes in the representation
+    const SIZE: usize;
+    /// Writes the little endian bytes to `out`, which is `SIZE` long
+    fn write_le(self, out: &mut [u8]);
+    /// Writes the big endian bytes to `out`, which is `SIZE` long
+    fn write_be(self, out: &mut [u8]);
+    /// Reads from little endian `bytes`, which is `SIZE` long
+    fn read_le(bytes: &[u8]) -> Self;
+    /// Reads from big endian `bytes`, which is `SIZE` long
+    fn read_be(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_fixed_bytes {
+    ($($t:ty),*) => {$(
+        impl sealed::Sealed for $t {}
+
+        impl FixedBytes for $t {
+            const SIZE: usize = core::mem::size_of::<$t>();
+            fn write_le(self, out: &mut [u8]) {
+                out.copy_from_slice(&self.to_le_bytes());
+            }
+            fn write_be(self, out: &mut [u8]) {
+                out.copy_from_slice(&self.to_be_bytes());
+            }
+            fn read_le(bytes: &[u8]) -> Self {
+                Self::from_le_bytes(bytes.try_into().unwrap())
+            }
+            fn read_be(bytes: &[u8]) -> Self {
+                Self::from_be_bytes(bytes.try_into().unwrap())
+            }
+        }
+    )*};
+}
+
+impl_fixed_bytes!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+fn base64_value(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+impl<const C: usize, L: LenType> ArrayVec<u8, C, L> {
+    /// Decodes hex text, either case
+    /// # Errors
+    /// If the text has an odd length, a non hex character or is too long
+    pub fn from_hex(hex: &str) -> Result<Self, DecodeError> {
+        let hex = hex.as_bytes();
+        if !hex.len().is_multiple_of(2) {
+            return Err(DecodeError::InvalidLength);
+        }
+        let mut ret = Self::new();
+        ret.try_reserve(hex.len() / 2)?;
+        for (i, pair) in hex.chunks_exact(2).enumerate() {
+            let hi = hex_value(pair[0]).ok_or(DecodeError::InvalidCharacter(i * 2))?;
+            let lo = hex_value(pair[1]).ok_or(DecodeError::InvalidCharacter(i * 2 + 1))?;
+            ret.push(hi << 4 | lo);
+        }
+        Ok(ret)
+    }
+
+    /// Appends the bytes as lowercase hex to `out`, which is left
+    /// untouched if it doesn't fit
+    /// # Errors
+    /// If `out` has less than `2 * self.len()` spare capacity
+    pub fn encode_hex_into<const M: usize, L2: LenType>(
+        &self,
+        out: &mut ArrayVec<u8, M, L2>,
+    ) -> Result<(), CapacityError> {
+        out.try_reserve(self.len() * 2)?;
+        for &byte in self {
+            out.push(HEX[usize::from(byte >> 4)]);
+            out.push(HEX[usize::from(byte & 0xf)]);
+        }
+        Ok(())
+    }
+
+    /// Decodes padded base64 text with the standard alphabet
+    /// # Errors
+    /// If the text isn't a multiple of 4 long, has a character outside the
+    /// alphabet, misplaced padding or non-zero bits before the padding,
+    /// or is too long
+    pub fn from_base64(base64: &str) -> Result<Self, DecodeError> {
+        let base64 = base64.as_bytes();
+        if !base64.len().is_multiple_of(4) {
+            return Err(DecodeError::InvalidLength);
+        }
+        let padding = base64.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 {
+            return Err(DecodeError::InvalidCharacter(base64.len() - padding));
+        }
+        let mut ret = Self::new();
+        ret.try_reserve(base64.len() / 4 * 3 - padding)?;
+        let quads = base64.len() / 4;
+        for (i, quad) in base64.chunks_exact(4).enumerate() {
+            let pad = if i + 1 == quads { padding } else { 0 };
+            let mut acc = 0u32;
+            for (j, &c) in quad.iter().enumerate() {
+                let value = if j >= 4 - pad {
+                    0
+                } else {
+                    base64_value(c).ok_or(DecodeError::InvalidCharacter(i * 4 + j))?
+                };
+                acc = acc << 6 | u32::from(value);
+            }
+            // the bits padding cuts off must be zero so each byte string
+            // only has one encoding
+            if acc & ((1 << (8 * pad)) - 1) != 0 {
+                return Err(DecodeError::InvalidCharacter(i * 4 + 3 - pad));
+            }
+            ret.extend(&acc.to_be_bytes()[1..4 - pad]);
+        }
+        Ok(ret)
+    }
+
+    /// Appends the bytes as padded base64 with the standard alphabet to `out`,
+    /// which is left untouched if it doesn't fit
+    /// # Errors
+    /// If `out` has less than `4 * ceil(self.len() / 3)` spare capacity
+    pub fn encode_base64_into<const M: usize, L2: LenType>(
+        &self,
+        out: &mut ArrayVec<u8, M, L2>,
+    ) -> Result<(), CapacityError> {
+        out.try_reserve(self.len().div_ceil(3) * 4)?;
+        for chunk in self.chunks(3) {
+            let mut bytes = [0; 4];
+            bytes[1..=chunk.len()].copy_from_slice(chunk);
+            let acc = u32::from_be_bytes(bytes);
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    out.push(BASE64[(acc >> (18 - 6 * i)) as usize & 63]);
+                } else {
+                    out.push(b'=');
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: FixedBytes, const C: usize, L: LenType> ArrayVec<T, C, L> {
+    fn encode_bytes_into<const M: usize, L2: LenType>(
+        &self,
+        out: &mut ArrayVec<u8, M, L2>,
+        write: fn(T, &mut [u8]),
+    ) -> Result<(), CapacityError> {
+        let start = out.len();
+        out.try_reserve(self.len() * T::SIZE)?;
+        out.extend(iter::repeat_n(0, self.len() * T::SIZE));
+        for (&x, bytes) in self.iter().zip(out[start..].chunks_exact_mut(T::SIZE)) {
+            write(x, bytes);
+        }
+        Ok(())
+    }
+
+    fn decode_bytes(bytes: &[u8], read: fn(&[u8]) -> T) -> Result<Self, DecodeError> {
+        if !bytes.len().is_multiple_of(T::SIZE) {
+            return Err(DecodeError::InvalidLength);
+        }
+        let mut ret = Self::new();
+        ret.try_reserve(bytes.len() / T::SIZE)?;
+        ret.extend(bytes.chunks_exact(T::SIZE).map(read));
+        Ok(ret)
+    }
+
+    /// Appends the elements as little endian bytes to `out`, which is
+    /// left untouched if they don't fit
+    /// # Errors
+    /// If `out` has less than `T::SIZE * self.len()` spare capacity
+    pub fn to_le_bytes_into<const M: usize, L2: LenType>(
+        &self,
+        out: &mut ArrayVec<u8, M, L2>,
+    ) -> Result<(), CapacityError> {
+        self.encode_bytes_into(out, T::write_le)
+    }
+
+    /// Appends the elements as big endian bytes to `out`, which is
+    /// left untouched if they don't fit
+    /// # Errors
+    /// If `out` has less than `T::SIZE * self.len()` spare capacity
+    pub fn to_be_bytes_into<const M: usize, L2: LenType>(
+        &self,
+        out: &mut ArrayVec<u8, M, L2>,
+    ) -> Result<(), CapacityError> {
+        self.encode_bytes_into(out, T::write_be)
+    }
+
+    /// Decodes little endian elements from `bytes`
+    /// # Errors
+    /// If the length isn't a multiple of `T::SIZE` or there are too many elements
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Self::decode_bytes(bytes, T::read_le)
+    }
+
+    /// Decodes big endian elements from `bytes`
+    /// # Errors
+    /// If the length isn't a multiple of `T::SIZE` or there are too many elements
+    pub fn from_be_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        Self::decode_bytes(bytes, T::read_be)
+    }
+}
@@ -0,0 +1,68 @@
+use core::fmt;
+
+/// Error for when an operation needs more room than the capacity allows
+///
+/// Holds the element that didn't fit when there is one
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CapacityError<T = ()> {
+    element: T,
+}
+
+impl<T> CapacityError<T> {
+    pub const fn new(element: T) -> Self {
+        Self { element }
+    }
+
+    /// The element that didn't fit
+    pub fn element(self) -> T {
+        self.element
+    }
+
+    /// Discards the element
+    pub fn simplify(self) -> CapacityError {
+        CapacityError { element: () }
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("insufficient capacity")
+    }
+}
+
+impl<T> fmt::Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CapacityError: insufficient capacity")
+    }
+}
+
+impl<T> core::error::Error for CapacityError<T> {}
+
+/// Error from [`ArrayVec::try_extend_exact`](crate::ArrayVec::try_extend_exact)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtendExactError {
+    /// The iterator ran out after this many items
+    TooFew(usize),
+    /// The iterator had more items than asked for
+    TooMany,
+    /// The items wouldn't fit in the vector
+    Capacity(CapacityError),
+}
+
+impl fmt::Display for ExtendExactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFew(got) => write!(f, "iterator ran out after {got} items"),
+            Self::TooMany => f.write_str("iterator had too many items"),
+            Self::Capacity(err) => err.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for ExtendExactError {}
+
+impl From<CapacityError> for ExtendExactError {
+    fn from(err: CapacityError) -> Self {
+        Self::Capacity(err)
+    }
+}
@@ -0,0 +1,111 @@
+use alloc::vec::Vec;
+use core::{alloc::Allocator, fmt, mem, ops};
+
+use crate::ArrayVec;
+
+/// Vector that stores up to `C` elements inline and moves them all into
+/// an allocation from `A` the first time it overflows
+///
+/// Only allocates once the inline storage is full, so arena allocators are
+/// only touched in the rare overflowing case
+pub struct FallbackVec<T, const C: usize, A: Allocator> {
+    inline: ArrayVec<T, C>,
+    // empty and unallocated until spilled, then holds every element
+    heap: Vec<T, A>,
+    spilled: bool,
+}
+
+impl<T, const C: usize, A: Allocator> FallbackVec<T, C, A> {
+    /// Creates a new empty `FallbackVec` which will spill into `alloc`
+    pub fn new_in(alloc: A) -> Self {
+        Self {
+            inline: ArrayVec::new(),
+            heap: Vec::new_in(alloc),
+            spilled: false,
+        }
+    }
+
+    /// Whether the elements have moved into the allocator
+    pub fn is_spilled(&self) -> bool {
+        self.spilled
+    }
+
+    /// Appends an item to the end, spilling if the inline storage is full
+    ///
+    /// Like [`Vec::push`], aborts through [`handle_alloc_error`] if `A` fails to allocate
+    ///
+    /// [`handle_alloc_error`]: alloc::alloc::handle_alloc_error
+    /// # Panics
+    /// If the new capacity exceeds `isize::MAX` bytes
+    pub fn push(&mut self, item: T) {
+        if self.spilled {
+            self.heap.push(item);
+            return;
+        }
+        if let Err(err) = self.inline.try_push(item) {
+            self.heap.reserve(C.max(1) * 2);
+            self.heap.extend(mem::take(&mut self.inline));
+            self.heap.push(err.element());
+            self.spilled = true;
+        }
+    }
+
+    /// Removes and returns the last element
+    pub fn pop(&mut self) -> Option<T> {
+        if self.spilled {
+            self.heap.pop()
+        } else {
+            self.inline.pop()
+        }
+    }
+
+    /// Removes all elements, keeping any allocation
+    pub fn clear(&mut self) {
+        self.inline.clear();
+        self.heap.clear();
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        if self.spilled {
+            &self.heap
+        } else {
+            &self.inline
+        }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        if self.spilled {
+            &mut self.heap
+        } else {
+            &mut self.inline
+        }
+    }
+}
+
+impl<T, const C: usize, A: Allocator> ops::Deref for FallbackVec<T, C, A> {
+    type Target = [T];
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T, const C: usize, A: Allocator> ops::DerefMut for FallbackVec<T, C, A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const C: usize, A: Allocator> Extend<T> for FallbackVec<T, C, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|item| self.push(item));
+    }
+}
+
+impl<T, const C: usize, A: Allocator> fmt::Debug for FallbackVec<T, C, A>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    aligned::{AlignedArrayVec, Alignment},
+    policy::{OverflowPolicy, PolicyVec},
+    ArrayVec, CapacityError, LenType,
+};
+
+/// Something push-able with a bounded capacity,
+/// for code generic over the concrete vector type
+pub trait FixedCapacityVec<T> {
+    /// The maximum number of elements that can be stored
+    fn capacity(&self) -> usize;
+
+    fn as_slice(&self) -> &[T];
+
+    fn as_mut_slice(&mut self) -> &mut [T];
+
+    /// Appends an item to the end
+    /// # Errors
+    /// Gives back `item` if full
+    fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>>;
+
+    /// Removes and returns the last element
+    fn pop(&mut self) -> Option<T>;
+
+    /// Appends an item to the end
+    /// # Panics
+    /// If full
+    fn push(&mut self, item: T) {
+        assert!(self.try_push(item).is_ok(), "vector full");
+    }
+
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+}
+
+impl<T, const C: usize, L: LenType> FixedCapacityVec<T> for ArrayVec<T, C, L> {
+    fn capacity(&self) -> usize {
+        C
+    }
+
+    fn as_slice(&self) -> &[T] {
+        ArrayVec::as_slice(self)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        ArrayVec::as_mut_slice(self)
+    }
+
+    fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        ArrayVec::try_push(self, item)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        ArrayVec::pop(self)
+    }
+
+    fn push(&mut self, item: T) {
+        ArrayVec::push(self, item);
+    }
+}
+
+/// `try_push` fails when full whatever the policy, `push` follows the policy
+impl<T, const C: usize, P: OverflowPolicy, L: LenType> FixedCapacityVec<T>
+    for PolicyVec<T, C, P, L>
+{
+    fn capacity(&self) -> usize {
+        C
+    }
+
+    fn as_slice(&self) -> &[T] {
+        ArrayVec::as_slice(self)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        PolicyVec::as_mut_slice(self)
+    }
+
+    fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(item));
+        }
+        PolicyVec::push(self, item);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        PolicyVec::pop(self)
+    }
+
+    fn push(&mut self, item: T) {
+        PolicyVec::push(self, item);
+    }
+}
+
+impl<T, const C: usize, A: Alignment, L: LenType> FixedCapacityVec<T>
+    for AlignedArrayVec<T, C, A, L>
+{
+    fn capacity(&self) -> usize {
+        C
+    }
+
+    fn as_slice(&self) -> &[T] {
+        ArrayVec::as_slice(self)
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        ArrayVec::as_mut_slice(self)
+    }
+
+    fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        ArrayVec::try_push(self, item)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        ArrayVec::pop(self)
+    }
+
+    fn push(&mut self, item: T) {
+        ArrayVec::push(self, item);
+    }
+}
+
+/// Unbounded, `capacity` is `usize::MAX` and `try_push` never fails
+#[cfg(feature = "alloc")]
+impl<T> FixedCapacityVec<T> for alloc::vec::Vec<T> {
+    fn capacity(&self) -> usize {
+        usize::MAX
+    }
+
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.push(item);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn push(&mut self, item: T) {
+        self.push(item);
+    }
+}
+
+/// Unbounded once spilled, `capacity` is `usize::MAX` and `try_push` never fails
+#[cfg(feature = "allocator_api")]
+impl<T, const C: usize, A: core::alloc::Allocator> FixedCapacityVec<T>
+    for crate::FallbackVec<T, C, A>
+{
+    fn capacity(&self) -> usize {
+        usize::MAX
+    }
+
+    fn as_slice(&self) -> &[T] {
+        self.as_slice()
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+
+    fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.push(item);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn push(&mut self, item: T) {
+        self.push(item);
+    }
+}
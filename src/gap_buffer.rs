@@ -0,0 +1,227 @@
+use core::{fmt, iter, mem::MaybeUninit, ptr, slice};
+
+use crate::CapacityError;
+
+/// Stack allocated gap buffer with capacity `C`
+///
+/// Elements before the cursor sit at the start of the buffer and elements
+/// after it at the end, so inserting and deleting at the cursor is O(1)
+/// and moving the cursor is O(distance moved)
+pub struct ArrayGapBuffer<T, const C: usize> {
+    data: [MaybeUninit<T>; C],
+    // elements before the cursor are 0..front
+    front: usize,
+    // elements after the cursor are C - back..C
+    back: usize,
+}
+
+impl<T, const C: usize> ArrayGapBuffer<T, C> {
+    /// Creates a new empty `ArrayGapBuffer`
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            // SAFETY: this array needs no initialisation because its uninitialised memory
+            data: unsafe { MaybeUninit::<[MaybeUninit<T>; C]>::uninit().assume_init() },
+            front: 0,
+            back: 0,
+        }
+    }
+
+    /// The maximum number of elements the buffer can store
+    #[allow(clippy::unused_self)]
+    pub const fn capacity(&self) -> usize {
+        C
+    }
+
+    /// The current number of elements the buffer stores
+    pub const fn len(&self) -> usize {
+        self.front + self.back
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len() == C
+    }
+
+    /// The position of the cursor, i.e. the number of elements before it
+    pub const fn cursor(&self) -> usize {
+        self.front
+    }
+
+    const fn as_ptr(&self) -> *const T {
+        self.data.as_ptr().cast::<T>()
+    }
+
+    const fn as_mut_ptr(&mut self) -> *mut T {
+        self.data.as_mut_ptr().cast::<T>()
+    }
+
+    /// The elements before the cursor
+    pub const fn before(&self) -> &[T] {
+        // SAFETY: 0..front is initialised
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.front) }
+    }
+
+    /// The elements after the cursor
+    pub const fn after(&self) -> &[T] {
+        // SAFETY: C - back..C is initialised
+        unsafe { slice::from_raw_parts(self.as_ptr().add(C - self.back), self.back) }
+    }
+
+    /// The elements before and after the cursor
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = (self.front, self.back);
+        let ptr = self.as_mut_ptr();
+        // SAFETY: both ranges are initialised and don't overlap
+        unsafe {
+            (
+                slice::from_raw_parts_mut(ptr, front),
+                slice::from_raw_parts_mut(ptr.add(C - back), back),
+            )
+        }
+    }
+
+    pub fn iter(&self) -> iter::Chain<slice::Iter<'_, T>, slice::Iter<'_, T>> {
+        self.before().iter().chain(self.after())
+    }
+
+    /// Inserts `item` before the cursor, leaving the cursor after it
+    /// # Panics
+    /// If the buffer is full
+    pub fn insert(&mut self, item: T) {
+        assert!(self.try_insert(item).is_ok(), "gap buffer full");
+    }
+
+    /// Inserts `item` before the cursor, leaving the cursor after it
+    /// # Errors
+    /// Gives back `item` if the buffer is full
+    pub fn try_insert(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(item));
+        }
+        // SAFETY: not full so front is in the gap
+        unsafe { self.as_mut_ptr().add(self.front).write(item) };
+        self.front += 1;
+        Ok(())
+    }
+
+    /// Removes and returns the element before the cursor, like backspace
+    pub fn delete_before(&mut self) -> Option<T> {
+        (self.front != 0).then(|| {
+            self.front -= 1;
+            // SAFETY: front was the last initialised index before the gap
+            unsafe { self.as_ptr().add(self.front).read() }
+        })
+    }
+
+    /// Removes and returns the element after the cursor, like delete
+    pub fn delete_after(&mut self) -> Option<T> {
+        (self.back != 0).then(|| {
+            let index = C - self.back;
+            self.back -= 1;
+            // SAFETY: index was the first initialised index after the gap
+            unsafe { self.as_ptr().add(index).read() }
+        })
+    }
+
+    /// Moves the cursor one element left, `false` if already at the start
+    pub fn move_left(&mut self) -> bool {
+        let moved = self.front != 0;
+        if moved {
+            self.set_cursor(self.front - 1);
+        }
+        moved
+    }
+
+    /// Moves the cursor one element right, `false` if already at the end
+    pub fn move_right(&mut self) -> bool {
+        let moved = self.back != 0;
+        if moved {
+            self.set_cursor(self.front + 1);
+        }
+        moved
+    }
+
+    /// Moves the cursor to `pos`, shifting the elements in between across the gap
+    /// # Panics
+    /// If `pos > self.len()`
+    pub fn set_cursor(&mut self, pos: usize) {
+        let len = self.len();
+        assert!(pos <= len, "cursor is {pos} but length is {len}");
+        let ptr = self.as_mut_ptr();
+        if pos < self.front {
+            let moved = self.front - pos;
+            // SAFETY: pos..front is initialised and C - back - moved..C - back
+            // is in the gap, ptr::copy as they overlap when the gap is small
+            unsafe { ptr::copy(ptr.add(pos), ptr.add(C - self.back - moved), moved) };
+            self.back += moved;
+        } else {
+            let moved = pos - self.front;
+            // SAFETY: C - back..C - back + moved is initialised and
+            // front..front + moved is in the gap
+            unsafe { ptr::copy(ptr.add(C - self.back), ptr.add(self.front), moved) };
+            self.back -= moved;
+        }
+        self.front = pos;
+    }
+
+    /// Removes all elements, leaving the cursor at 0
+    pub fn clear(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let (front, back) = (ptr::from_mut(front), ptr::from_mut(back));
+        self.front = 0;
+        self.back = 0;
+        // SAFETY: both halves were initialised and are now outside the
+        // tracked ranges so can't be dropped twice
+        unsafe {
+            let _back = DropSlice(back);
+            ptr::drop_in_place(front);
+        }
+    }
+}
+
+/// Drops the slice when dropped, so it's dropped even if
+/// dropping something before it panics
+struct DropSlice<T>(*mut [T]);
+
+impl<T> Drop for DropSlice<T> {
+    fn drop(&mut self) {
+        // SAFETY: made from an initialised slice that nothing else drops
+        unsafe { ptr::drop_in_place(self.0) };
+    }
+}
+
+impl<T, const C: usize> Drop for ArrayGapBuffer<T, C> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const C: usize> Default for ArrayGapBuffer<T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const C: usize> fmt::Debug for ArrayGapBuffer<T, C>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArrayGapBuffer")
+            .field("before", &self.before())
+            .field("after", &self.after())
+            .finish()
+    }
+}
+
+impl<'a, T, const C: usize> IntoIterator for &'a ArrayGapBuffer<T, C> {
+    type Item = &'a T;
+    type IntoIter = iter::Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
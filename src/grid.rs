@@ -0,0 +1,150 @@
+use core::{array, fmt, slice};
+
+use crate::{ArrayVec, CapacityError};
+
+/// Stack allocated 2D grid of up to `H` rows of `W` elements
+///
+/// Rows are initialised one at a time from the top, so a grid can be built
+/// row by row, cells are addressed by column `x` and row `y`
+pub struct ArrayGrid<T, const W: usize, const H: usize> {
+    rows: ArrayVec<[T; W], H>,
+}
+
+impl<T, const W: usize, const H: usize> ArrayGrid<T, W, H> {
+    /// Creates a new grid with no rows
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            rows: ArrayVec::new(),
+        }
+    }
+
+    /// Creates a complete grid with `f(x, y)` in every cell
+    pub fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> T,
+    {
+        let mut ret = Self::new();
+        for y in 0..H {
+            ret.rows.push(array::from_fn(|x| f(x, y)));
+        }
+        ret
+    }
+
+    #[allow(clippy::unused_self)]
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    /// The number of rows initialised so far
+    pub const fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether all `H` rows are initialised
+    pub const fn is_complete(&self) -> bool {
+        self.rows.is_full()
+    }
+
+    /// Adds a row below the others
+    /// # Errors
+    /// Gives back `row` if the grid is complete
+    pub fn push_row(&mut self, row: [T; W]) -> Result<(), CapacityError<[T; W]>> {
+        self.rows.try_push(row)
+    }
+
+    /// Removes and returns the bottom row
+    pub fn pop_row(&mut self) -> Option<[T; W]> {
+        self.rows.pop()
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.rows.get(y)?.get(x)
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.rows.get_mut(y)?.get_mut(x)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T; W]> {
+        self.rows.get(y)
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T; W]> {
+        self.rows.get_mut(y)
+    }
+
+    /// The initialised rows, top first
+    pub fn rows(&self) -> slice::Iter<'_, [T; W]> {
+        self.rows.iter()
+    }
+
+    pub fn rows_mut(&mut self) -> slice::IterMut<'_, [T; W]> {
+        self.rows.iter_mut()
+    }
+
+    /// The cells of column `x` in the initialised rows, top first
+    /// # Panics
+    /// If `x >= W`
+    pub fn column(&self, x: usize) -> impl ExactSizeIterator<Item = &T> + DoubleEndedIterator {
+        assert!(x < W, "column is {x} but width is {W}");
+        self.rows.iter().map(move |row| &row[x])
+    }
+
+    /// Swaps two cells
+    /// # Panics
+    /// If either is out of bounds
+    pub fn swap(&mut self, (x1, y1): (usize, usize), (x2, y2): (usize, usize)) {
+        let height = self.height();
+        assert!(
+            x1 < W && x2 < W && y1 < height && y2 < height,
+            "swapping ({x1}, {y1}) and ({x2}, {y2}) but the grid is {W}x{height}"
+        );
+        self.rows.as_flattened_mut().swap(y1 * W + x1, y2 * W + x2);
+    }
+
+    /// Removes all rows
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+}
+
+impl<T, const W: usize, const H: usize> ArrayGrid<T, W, H>
+where
+    T: Clone,
+{
+    /// Sets every cell to `value`, completing the grid
+    pub fn fill(&mut self, value: T) {
+        self.rows.as_flattened_mut().fill(value.clone());
+        while !self.rows.is_full() {
+            self.rows.push(array::from_fn(|_| value.clone()));
+        }
+    }
+}
+
+impl<T, const W: usize, const H: usize> Default for ArrayGrid<T, W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const W: usize, const H: usize> Clone for ArrayGrid<T, W, H>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            rows: self.rows.clone(),
+        }
+    }
+}
+
+impl<T, const W: usize, const H: usize> fmt::Debug for ArrayGrid<T, W, H>
+where
+    T: fmt::Debug,
+{
+    /// Formats as a list of rows
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
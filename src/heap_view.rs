@@ -0,0 +1,121 @@
+use core::fmt;
+
+use crate::{ArrayVec, CapacityError, LenType};
+
+/// Max-heap over a borrowed [`ArrayVec`], the elements are left in heap
+/// order and usable as a plain vector again once the view is dropped
+pub struct BinaryHeapView<'a, T: Ord, const C: usize, L: LenType = usize> {
+    vec: &'a mut ArrayVec<T, C, L>,
+}
+
+impl<'a, T: Ord, const C: usize, L: LenType> BinaryHeapView<'a, T, C, L> {
+    /// Rearranges the elements into a heap in O(n)
+    pub fn new(vec: &'a mut ArrayVec<T, C, L>) -> Self {
+        let mut heap = Self { vec };
+        for i in (0..heap.len() / 2).rev() {
+            heap.sift_down(i, heap.len());
+        }
+        heap
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    /// The greatest element
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.vec.first()
+    }
+
+    /// Adds an element in O(log n)
+    /// # Panics
+    /// If the vector is full
+    pub fn push(&mut self, item: T) {
+        assert!(self.try_push(item).is_ok(), "stackvec full");
+    }
+
+    /// Adds an element in O(log n)
+    /// # Errors
+    /// Gives back `item` if the vector is full
+    pub fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.vec.try_push(item)?;
+        self.sift_up(self.len() - 1);
+        Ok(())
+    }
+
+    /// Removes and returns the greatest element in O(log n)
+    pub fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        self.vec.swap(0, len - 1);
+        let top = self.vec.pop();
+        self.sift_down(0, len - 1);
+        top
+    }
+
+    /// The elements in heap order
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.vec
+    }
+
+    /// Sorts the elements ascending, ending the view
+    pub fn into_sorted(mut self) {
+        for end in (1..self.len()).rev() {
+            self.vec.swap(0, end);
+            self.sift_down(0, end);
+        }
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.vec[i] <= self.vec[parent] {
+                break;
+            }
+            self.vec.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    /// Sifts `i` down within the heap `0..end`
+    fn sift_down(&mut self, mut i: usize, end: usize) {
+        loop {
+            let left = 2 * i + 1;
+            if left >= end {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < end && self.vec[right] > self.vec[left] {
+                right
+            } else {
+                left
+            };
+            if self.vec[i] >= self.vec[child] {
+                break;
+            }
+            self.vec.swap(i, child);
+            i = child;
+        }
+    }
+}
+
+impl<T: Ord, const C: usize, L: LenType> fmt::Debug for BinaryHeapView<'_, T, C, L>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BinaryHeapView")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
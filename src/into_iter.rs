@@ -0,0 +1,90 @@
+use core::{fmt, iter::FusedIterator, ptr, slice};
+
+use crate::{ArrayVec, LenType, PoisonGuard};
+
+/// Owning iterator over the elements of an [`ArrayVec`]
+pub struct IntoIter<T, const C: usize, L: LenType = usize> {
+    // length is zeroed so only start..end are tracked as initialised
+    vec: ArrayVec<T, C, L>,
+    start: usize,
+    end: usize,
+}
+
+impl<T, const C: usize, L: LenType> IntoIter<T, C, L> {
+    pub(crate) fn new(mut vec: ArrayVec<T, C, L>) -> Self {
+        let end = vec.len();
+        // SAFETY: the iterator takes over dropping 0..end
+        unsafe { vec.set_len(0) };
+        Self { vec, start: 0, end }
+    }
+
+    /// The remaining elements as a slice
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: start..end are initialised
+        unsafe { slice::from_raw_parts(self.vec.as_ptr().add(self.start), self.end - self.start) }
+    }
+}
+
+impl<T, const C: usize, L: LenType> Iterator for IntoIter<T, C, L> {
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        (self.start != self.end).then(|| {
+            self.start += 1;
+            // SAFETY: start - 1 was initialised and is now out of start..end
+            let item = unsafe { self.vec.take(self.start - 1) };
+            // SAFETY: start - 1 was just moved out
+            unsafe { self.vec.poison(self.start - 1, self.start) };
+            item
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T, const C: usize, L: LenType> DoubleEndedIterator for IntoIter<T, C, L> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        (self.start != self.end).then(|| {
+            self.end -= 1;
+            // SAFETY: end was initialised and is now out of start..end
+            let item = unsafe { self.vec.take(self.end) };
+            // SAFETY: end was just moved out
+            unsafe { self.vec.poison(self.end, self.end + 1) };
+            item
+        })
+    }
+}
+
+impl<T, const C: usize, L: LenType> ExactSizeIterator for IntoIter<T, C, L> {}
+
+impl<T, const C: usize, L: LenType> FusedIterator for IntoIter<T, C, L> {}
+
+impl<T, const C: usize, L: LenType> Drop for IntoIter<T, C, L> {
+    fn drop(&mut self) {
+        // poisons the remaining slots even if dropping one panics
+        let guard = PoisonGuard {
+            vec: &mut self.vec,
+            from: self.start,
+            to: self.end,
+        };
+        let remaining = ptr::slice_from_raw_parts_mut(
+            // SAFETY: start <= end <= C
+            unsafe { guard.vec.as_mut_ptr().add(self.start) },
+            self.end - self.start,
+        );
+        // SAFETY: start..end are initialised and not read again,
+        // the vector's own length is zero so it won't drop them twice
+        unsafe { ptr::drop_in_place(remaining) };
+    }
+}
+
+impl<T, const C: usize, L: LenType> fmt::Debug for IntoIter<T, C, L>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
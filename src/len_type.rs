@@ -0,0 +1,81 @@
+use core::{mem, ptr};
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+    impl Sealed for usize {}
+}
+
+/// Integer type used to store the length of an [`ArrayVec`](crate::ArrayVec)
+///
+/// Picking the smallest type that fits the capacity shrinks the struct,
+/// e.g. `ArrayVec<u8, 8, u8>` is 9 bytes rather than 16 on 64-bit targets.
+/// Implemented for `u8`, `u16`, `u32` and `usize`, this trait is sealed.
+pub trait LenType: Copy + sealed::Sealed {
+    /// The largest length this type can store
+    const MAX: usize;
+    /// A length of zero
+    const ZERO: Self;
+}
+
+impl LenType for u8 {
+    const MAX: usize = u8::MAX as usize;
+    const ZERO: Self = 0;
+}
+
+impl LenType for u16 {
+    const MAX: usize = u16::MAX as usize;
+    const ZERO: Self = 0;
+}
+
+impl LenType for u32 {
+    #[allow(clippy::cast_possible_truncation)] // saturates below instead
+    const MAX: usize = if mem::size_of::<usize>() < 4 {
+        usize::MAX
+    } else {
+        u32::MAX as usize
+    };
+    const ZERO: Self = 0;
+}
+
+impl LenType for usize {
+    const MAX: usize = usize::MAX;
+    const ZERO: Self = 0;
+}
+
+// trait methods can't be called in a const fn so the conversions
+// dispatch on the size of the type instead
+
+/// Reads `len` as a `usize`
+pub(crate) const fn to_usize<L: LenType>(len: &L) -> usize {
+    let ptr = ptr::from_ref(len);
+    // SAFETY: LenType is sealed and only implemented for unsigned integers,
+    // every one of those with the same size has the same representation
+    unsafe {
+        match mem::size_of::<L>() {
+            1 => *ptr.cast::<u8>() as usize,
+            2 => *ptr.cast::<u16>() as usize,
+            4 => *ptr.cast::<u32>() as usize,
+            _ => *ptr.cast::<usize>(),
+        }
+    }
+}
+
+/// Stores `len` into `dst`
+///
+/// `len` must be <= `L::MAX` or it will be truncated
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) const fn set_from_usize<L: LenType>(dst: &mut L, len: usize) {
+    let ptr = ptr::from_mut(dst);
+    // SAFETY: see `to_usize`
+    unsafe {
+        match mem::size_of::<L>() {
+            1 => *ptr.cast::<u8>() = len as u8,
+            2 => *ptr.cast::<u16>() = len as u16,
+            4 => *ptr.cast::<u32>() = len as u32,
+            _ => *ptr.cast::<usize>() = len,
+        }
+    }
+}
@@ -1,24 +1,121 @@
 #![no_std]
 #![warn(clippy::pedantic)]
+#![cfg_attr(feature = "nightly", feature(generic_const_exprs))]
+#![cfg_attr(feature = "nightly", allow(incomplete_features))]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::{
+    cell::Cell,
     fmt, iter,
     mem::{self, MaybeUninit},
-    ops, slice,
+    ops, ptr, slice,
 };
 
+mod aligned;
+#[cfg(target_has_atomic = "ptr")]
+pub mod byte_queue;
+pub mod codec;
+mod error;
+#[cfg(feature = "allocator_api")]
+mod fallback;
+mod fixed_capacity;
+mod gap_buffer;
+mod grid;
+mod heap_view;
+mod into_iter;
+mod len_type;
+#[cfg(feature = "nightly")]
+mod nightly;
+pub mod policy;
+mod retain_drain;
+mod sort;
+mod transaction;
+mod try_extend;
+#[cfg(kani)]
+mod verification;
+
+pub use aligned::{Align16, Align32, Align64, AlignedArrayVec, Alignment};
+#[cfg(target_has_atomic = "ptr")]
+pub use byte_queue::ByteQueue;
+pub use error::{CapacityError, ExtendExactError};
+#[cfg(feature = "allocator_api")]
+pub use fallback::FallbackVec;
+pub use fixed_capacity::FixedCapacityVec;
+pub use gap_buffer::ArrayGapBuffer;
+pub use grid::ArrayGrid;
+pub use heap_view::BinaryHeapView;
+pub use into_iter::IntoIter;
+pub use len_type::LenType;
+pub use retain_drain::RetainDrain;
+pub use transaction::Transaction;
+pub use try_extend::TryExtend;
+
+/// Byte written over freed slots with the `debug-invariants` feature
+#[cfg(feature = "debug-invariants")]
+const POISON: u8 = 0xa5;
+
 /// Stack allocated vector type with capacity `C`
-pub struct ArrayVec<T, const C: usize> {
+///
+/// The length is stored as an `L`, see [`LenType`]
+///
+/// # Layout
+/// Guaranteed to be laid out like the C struct
+/// ```c
+/// struct {
+///     T data[C];
+///     L len;
+/// };
+/// ```
+/// where only the first `len` elements of `data` are initialised
+#[repr(C)]
+pub struct ArrayVec<T, const C: usize, L: LenType = usize> {
     data: [MaybeUninit<T>; C],
-    write: usize,
+    write: L,
 }
 
-impl<T, const C: usize> ArrayVec<T, C> {
+impl<T, const C: usize, L: LenType> ArrayVec<T, C, L> {
+    /// An empty `ArrayVec`, for initialising `static` and `const` items
+    pub const NEW: Self = Self::new();
+
     /// Creates a new empty `ArrayVec`
+    /// # Panics
+    /// At compile time if `C` doesn't fit in `L`
     #[must_use]
-    pub fn new() -> Self {
+    pub const fn new() -> Self {
+        let () = Self::_C_FITS_LEN;
         // SAFETY: this array needs no initialisation because its uninitialised memory
         let data = unsafe { MaybeUninit::<[MaybeUninit<T>; C]>::uninit().assume_init() };
-        Self { data, write: 0 }
+        Self {
+            data,
+            write: L::ZERO,
+        }
+    }
+
+    /// Views an `ArrayVec` placed in memory by foreign code,
+    /// e.g. in shared memory or a memory-mapped region
+    /// # Safety
+    /// - `ptr` must be non null, aligned and valid for reads for `'a`
+    /// - The length field must be `<= C` and that many elements initialised
+    /// - The memory must not be mutated for `'a`
+    pub unsafe fn from_ptr<'a>(ptr: *const Self) -> &'a Self {
+        // SAFETY: upheld by the caller
+        let vec = unsafe { &*ptr };
+        debug_assert!(vec.len() <= C, "length field exceeds capacity");
+        vec
+    }
+
+    /// Mutable version of [`ArrayVec::from_ptr`]
+    /// # Safety
+    /// - `ptr` must be non null, aligned and valid for reads and writes for `'a`
+    /// - The length field must be `<= C` and that many elements initialised
+    /// - The memory must not be accessed through any other pointer for `'a`
+    pub unsafe fn from_mut_ptr<'a>(ptr: *mut Self) -> &'a mut Self {
+        // SAFETY: upheld by the caller
+        let vec = unsafe { &mut *ptr };
+        debug_assert!(vec.len() <= C, "length field exceeds capacity");
+        vec
     }
 
     /// The maximum number of elements the vector can store
@@ -29,33 +126,134 @@ impl<T, const C: usize> ArrayVec<T, C> {
 
     /// The current number of elements the vector stores
     pub const fn len(&self) -> usize {
-        self.write
+        len_type::to_usize(&self.write)
+    }
+
+    /// How many more elements the vector can store
+    pub const fn remaining_capacity(&self) -> usize {
+        C - self.len()
+    }
+
+    /// Checks there's room for `additional` more elements,
+    /// for code written against `Vec`'s reserve then extend pattern
+    /// # Errors
+    /// If `additional > self.remaining_capacity()`
+    pub const fn try_reserve(&self, additional: usize) -> Result<(), CapacityError> {
+        if additional > self.remaining_capacity() {
+            return Err(CapacityError::new(()));
+        }
+        Ok(())
     }
 
     pub const fn is_full(&self) -> bool {
-        self.write == C
+        self.len() == C
     }
 
     pub const fn is_empty(&self) -> bool {
-        self.write == 0
+        self.len() == 0
+    }
+
+    /// Sets the length without touching the elements
+    /// # Safety
+    /// - `len <= C`
+    /// - Indexes below `len` must be initialised
+    const unsafe fn set_len(&mut self, len: usize) {
+        #[cfg(feature = "debug-invariants")]
+        debug_assert!(len <= C, "length set past the capacity");
+        len_type::set_from_usize(&mut self.write, len);
+    }
+
+    /// Overwrites the uninitialised slots `from..to` with [`POISON`] so reads
+    /// of dropped elements stand out, does nothing without `debug-invariants`
+    /// # Safety
+    /// - `from..to` is within the capacity and not initialised
+    #[allow(unused_variables, clippy::unused_self)]
+    const unsafe fn poison(&mut self, from: usize, to: usize) {
+        #[cfg(feature = "debug-invariants")]
+        // SAFETY: the slots are uninitialised so any bytes are fine
+        unsafe {
+            ptr::write_bytes(self.as_mut_ptr().add(from), POISON, to - from);
+        }
+    }
+
+    /// Checks the internal invariants, for tests and debugging
+    /// # Panics
+    /// If the length is past the capacity
+    #[cfg(any(test, kani, feature = "debug-invariants"))]
+    pub fn assert_invariants(&self) {
+        let len = self.len();
+        assert!(len <= C, "length is {len} but capacity is {C}");
+    }
+
+    /// Raw pointer to the start of the buffer, valid for the whole capacity
+    ///
+    /// Only the first `self.len()` elements are initialised
+    pub const fn as_ptr(&self) -> *const T {
+        self.data.as_ptr().cast::<T>()
+    }
+
+    /// Raw mutable pointer to the start of the buffer, valid for the whole capacity
+    ///
+    /// Only the first `self.len()` elements are initialised
+    pub const fn as_mut_ptr(&mut self) -> *mut T {
+        self.data.as_mut_ptr().cast::<T>()
     }
 
     // Removes all elements from the vector
     pub fn clear(&mut self) {
-        // dropping all
-        // SAFETY: all indexes are < write so pointing to initialised memory
-        (0..self.write).for_each(|i| unsafe { drop(self.take(i)) });
-        self.write = 0;
+        self.truncate(0);
+    }
+
+    /// Shortens the vector to `len` elements, dropping the rest.
+    /// Does nothing if `len >= self.len()`
+    pub fn truncate(&mut self, len: usize) {
+        let old_len = self.len();
+        if len >= old_len {
+            return;
+        }
+        // SAFETY: len < old_len so the tail is initialised. The length is
+        // lowered first so a panicking Drop can't cause a double drop,
+        // drop_in_place still drops the rest of the slice if one panics
+        // and the guard poisons it either way
+        unsafe {
+            self.set_len(len);
+            let guard = PoisonGuard {
+                vec: self,
+                from: len,
+                to: old_len,
+            };
+            let tail =
+                ptr::slice_from_raw_parts_mut(guard.vec.as_mut_ptr().add(len), old_len - len);
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    /// Drops elements from the front so at most `len` are left, shifting the
+    /// rest down, does nothing if `len >= self.len()`
+    pub fn truncate_front(&mut self, len: usize) {
+        let removed = self.len().saturating_sub(len);
+        if removed == 0 {
+            return;
+        }
+        let mut guard = Compactor::new(self);
+        for _ in 0..removed {
+            // SAFETY: removed <= len
+            unsafe { guard.delete() };
+        }
+    }
+
+    /// Keeps only the `n` most recently pushed elements, see [`ArrayVec::truncate_front`]
+    pub fn keep_last(&mut self, n: usize) {
+        self.truncate_front(n);
     }
 
     /// Copys & returns the value at `index`
     /// # Safety
     /// - The value at `index` must be initialised
     /// - Cannot take from same index twice
-    unsafe fn take(&mut self, index: usize) -> T {
-        let pos = &mut self.data[index];
-        let ret = mem::replace(pos, MaybeUninit::uninit());
-        ret.assume_init()
+    const unsafe fn take(&mut self, index: usize) -> T {
+        // SAFETY: index is initialised so also in bounds
+        unsafe { self.as_ptr().add(index).read() }
     }
 
     // TODO: try variants
@@ -63,18 +261,22 @@ impl<T, const C: usize> ArrayVec<T, C> {
     /// # Panics
     /// If `index >= self.len()` out of bounds
     pub fn remove(&mut self, index: usize) -> T {
-        assert!(
-            index < self.write,
-            "index is {index} but length is {0}",
-            self.write
-        );
-        // SAFETY: index is verified to be less than self.write above
-        let ret = unsafe { self.take(index) };
-        self.write -= 1;
-        // SAFETY: self.write has been decremented so it points to initialised memory
-        for i in index..self.write {
-            let next = unsafe { self.take(i + 1) };
-            self.data[i].write(next);
+        let len = self.len();
+        assert!(index < len, "index is {index} but length is {len}");
+        let ptr = self.as_mut_ptr();
+        // SAFETY: index < len so all of index..len is initialised,
+        // the read leaves a hole at index which the copy shifts the tail into.
+        // For zero sized T both of these are no-ops
+        let ret = unsafe {
+            let ret = ptr.add(index).read();
+            ptr::copy(ptr.add(index + 1), ptr.add(index), len - index - 1);
+            ret
+        };
+        // SAFETY: 0..len - 1 is initialised after the shift
+        // and len - 1 has been moved down
+        unsafe {
+            self.set_len(len - 1);
+            self.poison(len - 1, len);
         }
         ret
     }
@@ -83,124 +285,561 @@ impl<T, const C: usize> ArrayVec<T, C> {
     /// # Panics
     /// If `index >= self.len()` out of bounds
     pub fn swap_remove(&mut self, index: usize) -> T {
-        assert!(
-            index < self.write,
-            "index is {index} but length is {0}",
-            self.write
-        );
-        // SAFETY: index is verified to be less than self.write above
-        let ret = unsafe { self.take(index) };
-        self.write -= 1;
-        // SAFETY: self.write has been decremented so it points to initialised memory
-        let last = unsafe { self.take(self.write) };
-        self.data[index].write(last);
+        let len = self.len();
+        assert!(index < len, "index is {index} but length is {len}");
+        let ptr = self.as_mut_ptr();
+        // SAFETY: index and len - 1 are initialised, the last element
+        // fills the hole (copying onto itself when index == len - 1)
+        let ret = unsafe {
+            let ret = ptr.add(index).read();
+            ptr::copy(ptr.add(len - 1), ptr.add(index), 1);
+            ret
+        };
+        // SAFETY: the last element has been moved out
+        unsafe {
+            self.set_len(len - 1);
+            self.poison(len - 1, len);
+        }
         ret
     }
 
-    /// Insert `item` at `index`
+    /// Insert `item` at `index`, shifting everything after it up
     /// # Panics
-    /// If `index >= self.len()` out of bounds
+    /// - If `index > self.len()` out of bounds
+    /// - If the vector is full
     pub fn insert(&mut self, index: usize, item: T) {
-        assert!(
-            index < self.write,
-            "index is {index} but length is {0}",
-            self.write
-        );
-        // starting at the end and copying to the next index
-        // if it weren't reversed this would just make the whole
-        // rest of the array be whatever item was inserted at
-        for i in (index..self.write).rev() {
-            // SAFETY: i < self.write as the reversed range starts
-            // at self.write - 1
-            let val = unsafe { self.take(i) };
-            // LEAK: writes to the next index in memory which
-            // is deinitialised either because it is at self.write
-            // or has been deinitalised in the previous iteration
-            self.data[i + 1].write(val);
-        }
-        self.write += 1;
-        // LEAK: data at index has been shifted forward
-        // so data[index] is deinitialised
-        self.data[index].write(item);
-    }
-    // TODO: not the implementation of this worst case O(N^2)
+        let len = self.len();
+        assert!(index <= len, "index is {index} but length is {len}");
+        assert!(len != C, "stackvec full");
+        // SAFETY: index <= len < C checked above
+        unsafe { self.insert_unchecked(index, item) };
+    }
+
+    /// Insert `item` at `index`
+    /// # Safety
+    /// - `index <= self.len()`
+    /// - The vector isn't full
+    unsafe fn insert_unchecked(&mut self, index: usize, item: T) {
+        let len = self.len();
+        let ptr = self.as_mut_ptr();
+        // SAFETY: len < C so there's room to shift index..len up by one,
+        // which leaves index free to be written
+        unsafe {
+            ptr::copy(ptr.add(index), ptr.add(index + 1), len - index);
+            ptr.add(index).write(item);
+        }
+        // SAFETY: 0..len + 1 is initialised
+        unsafe { self.set_len(len + 1) };
+    }
+
+    /// Inserts `item` at the start, shifting everything up
+    /// # Panics
+    /// If the vector is full
+    pub fn push_front(&mut self, item: T) {
+        self.insert(0, item);
+    }
+
+    /// Inserts `item` at the start, shifting everything up
+    /// # Errors
+    /// Gives back `item` if the vector is full
+    pub fn try_push_front(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(item));
+        }
+        // SAFETY: 0 <= len and not full
+        unsafe { self.insert_unchecked(0, item) };
+        Ok(())
+    }
+
+    /// Removes and returns the first element, shifting everything down
+    pub fn pop_front(&mut self) -> Option<T> {
+        (!self.is_empty()).then(|| self.remove(0))
+    }
+
     /// Retains only the elements specified by the predicate.
     /// So where `f(element)` is true an element is kept in the list
     pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&T) -> bool,
     {
-        // not quite a for loop because it doesn't always advance
-        let mut i = 0;
-        while i < self.write {
-            // SAFETY: i < self.write above
-            let val = unsafe { self.data[i as usize].assume_init_ref() };
-            if f(val) {
-                // retain, move to next
-                i += 1;
+        self.retain_enumerated(|_, item| f(item));
+    }
+
+    /// Like [`ArrayVec::retain`] but `f` also gets each element's
+    /// index from before any were removed, and can mutate it
+    pub fn retain_enumerated<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &mut T) -> bool,
+    {
+        let mut guard = Compactor::new(self);
+        while guard.processed < guard.len {
+            // SAFETY: processed < len
+            let cur = unsafe { guard.current() };
+            // SAFETY: cur is initialised
+            if f(guard.processed, unsafe { &mut *cur }) {
+                // SAFETY: processed < len
+                unsafe { guard.keep() };
             } else {
-                // remove, put next where
-                // current is
+                // SAFETY: processed < len
+                unsafe { guard.delete() };
+            }
+        }
+    }
 
-                self.remove(i as usize);
+    /// Replaces every element with the result of `f`, removing those it maps
+    /// to `None`, in one pass
+    pub fn retain_map<F>(&mut self, mut f: F)
+    where
+        F: FnMut(T) -> Option<T>,
+    {
+        let mut guard = Compactor::new(self);
+        while guard.processed < guard.len {
+            // SAFETY: processed < len
+            let item = unsafe { guard.take() };
+            if let Some(item) = f(item) {
+                // SAFETY: just taken
+                unsafe { guard.put(item) };
             }
         }
     }
+
+    /// Like [`ArrayVec::retain`] but yields the removed elements,
+    /// anything not yet visited when the iterator is dropped is kept
+    pub fn retain_drain<F>(&mut self, pred: F) -> RetainDrain<'_, T, C, L, F>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        RetainDrain::new(self, pred)
+    }
+
+    /// Removes consecutive elements where `same_bucket(element, previous_kept)`
+    /// is true, keeping the first of each run
+    pub fn dedup_by<F>(&mut self, mut same_bucket: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let len = self.len();
+        if len <= 1 {
+            return;
+        }
+        let mut guard = Compactor::new(self);
+        // the first element is always kept
+        guard.processed = 1;
+        while guard.processed < guard.len {
+            // SAFETY: processed < len, the last kept element is just below the holes
+            let (cur, prev) = unsafe {
+                let cur = guard.current();
+                (cur, cur.sub(guard.deleted + 1))
+            };
+            // SAFETY: cur and prev are distinct initialised elements
+            if same_bucket(unsafe { &mut *cur }, unsafe { &mut *prev }) {
+                // SAFETY: processed < len
+                unsafe { guard.delete() };
+            } else {
+                // SAFETY: processed < len
+                unsafe { guard.keep() };
+            }
+        }
+    }
+
+    /// Removes consecutive elements where `key` gives the same result
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        F: FnMut(&mut T) -> K,
+        K: PartialEq,
+    {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
     /// Appends an item to the end of the vector
+    ///
+    /// Usable in const contexts
     /// # Panics
     /// If the vector is full
-    pub fn push(&mut self, item: T) {
-        assert!(self.write != C, "stackvec full");
-        self.data[self.write].write(item);
-        self.write += 1;
-    }
-
-    /// Removes and returns
-    pub fn pop(&mut self) -> Option<T> {
-        (self.write != 0).then(|| {
-            self.write -= 1;
-            // SAFETY: self.write has been decremented
-            // so it now points to initialised memory,
-            // this deinitialises this memory
-            unsafe { self.take(self.write) }
-        })
+    pub const fn push(&mut self, item: T) {
+        let len = self.len();
+        assert!(len != C, "stackvec full");
+        // SAFETY: len < C so in bounds
+        unsafe { self.as_mut_ptr().add(len).write(item) };
+        // SAFETY: len < C and len was just written
+        unsafe { self.set_len(len + 1) };
     }
 
-    pub fn into_array(self) -> Option<[T; C]> {
-        (self.write == C).then(|| self.data.map(|i| unsafe { i.assume_init() }))
+    /// Appends an item to the end of the vector
+    ///
+    /// Usable in const contexts
+    /// # Errors
+    /// Gives back `item` if the vector is full
+    pub const fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        if self.is_full() {
+            return Err(CapacityError::new(item));
+        }
+        self.push(item);
+        Ok(())
+    }
+
+    /// Returns the first element matching `pred`,
+    /// or pushes and returns `make()` if there isn't one
+    /// # Errors
+    /// If nothing matches and the vector is full, `make` isn't called
+    pub fn find_or_push<P, F>(&mut self, pred: P, make: F) -> Result<&mut T, CapacityError>
+    where
+        P: FnMut(&T) -> bool,
+        F: FnOnce() -> T,
+    {
+        let index = match self.iter().position(pred) {
+            Some(index) => index,
+            None if self.is_full() => return Err(CapacityError::new(())),
+            None => {
+                self.push(make());
+                self.len() - 1
+            }
+        };
+        Ok(&mut self[index])
+    }
+
+    /// Appends exactly `n` items from `iter`, leaving the vector
+    /// untouched if it yields a different number or panics
+    ///
+    /// After taking `n` items one more is pulled from `iter` to check it's
+    /// finished, so that item is consumed and dropped when it's too long
+    /// # Errors
+    /// If there's no room for `n` more or `iter` doesn't yield exactly `n` items
+    pub fn try_extend_exact<I>(&mut self, iter: I, n: usize) -> Result<(), ExtendExactError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.try_reserve(n)?;
+        let mut iter = iter.into_iter();
+        let mut transaction = self.transaction();
+        iter.by_ref()
+            .take(n)
+            .for_each(|item| transaction.push(item));
+        let added = transaction.pushed().len();
+        if added < n {
+            return Err(ExtendExactError::TooFew(added));
+        }
+        if iter.next().is_some() {
+            return Err(ExtendExactError::TooMany);
+        }
+        transaction.commit();
+        Ok(())
+    }
+
+    /// Starts a group of pushes that are all removed again
+    /// unless [`Transaction::commit`] is called
+    pub fn transaction(&mut self) -> Transaction<'_, T, C, L> {
+        Transaction::new(self)
+    }
+
+    /// Moves all of `other`'s elements onto the end, leaving it empty
+    /// # Panics
+    /// If `other.len() > self.remaining_capacity()`
+    pub fn append<const C2: usize, L2: LenType>(&mut self, other: &mut ArrayVec<T, C2, L2>) {
+        assert!(self.try_append(other).is_ok(), "stackvec full");
+    }
+
+    /// Moves all of `other`'s elements onto the end, leaving it empty,
+    /// or leaves both untouched if they don't fit
+    /// # Errors
+    /// If `other.len() > self.remaining_capacity()`
+    pub fn try_append<const C2: usize, L2: LenType>(
+        &mut self,
+        other: &mut ArrayVec<T, C2, L2>,
+    ) -> Result<(), CapacityError> {
+        let (len, moved) = (self.len(), other.len());
+        self.try_reserve(moved)?;
+        // SAFETY: there's room for moved more and other's elements are
+        // forgotten by setting its length to 0
+        unsafe {
+            ptr::copy_nonoverlapping(other.as_ptr(), self.as_mut_ptr().add(len), moved);
+            other.set_len(0);
+            other.poison(0, moved);
+            self.set_len(len + moved);
+        }
+        Ok(())
+    }
+
+    /// Swaps the elements with `other`'s, leaving both untouched if either doesn't fit
+    /// # Errors
+    /// If `self.len() > C2` or `other.len() > C`
+    pub fn swap_contents<const C2: usize, L2: LenType>(
+        &mut self,
+        other: &mut ArrayVec<T, C2, L2>,
+    ) -> Result<(), CapacityError> {
+        let (len, other_len) = (self.len(), other.len());
+        if len > C2 || other_len > C {
+            return Err(CapacityError::new(()));
+        }
+        let common = len.min(other_len);
+        let (ptr, other_ptr) = (self.as_mut_ptr(), other.as_mut_ptr());
+        // SAFETY: both have common initialised elements, the longer one's
+        // tail fits in the other's capacity and the lengths are swapped after
+        unsafe {
+            ptr::swap_nonoverlapping(ptr, other_ptr, common);
+            if len > common {
+                ptr::copy_nonoverlapping(ptr.add(common), other_ptr.add(common), len - common);
+            } else {
+                ptr::copy_nonoverlapping(
+                    other_ptr.add(common),
+                    ptr.add(common),
+                    other_len - common,
+                );
+            }
+            self.set_len(other_len);
+            other.set_len(len);
+            self.poison(common, len);
+            other.poison(common, other_len);
+        }
+        Ok(())
     }
 
-    pub fn resize<const NEW_C: usize>(mut self) -> Option<ArrayVec<T, NEW_C>> {
-        if self.write > NEW_C {
+    /// Removes and returns the last element
+    ///
+    /// Usable in const contexts
+    pub const fn pop(&mut self) -> Option<T> {
+        let len = self.len();
+        if len == 0 {
             return None;
         }
-        // SAFETY: Doesn't need initialisation as its already initialised
-        let mut data = unsafe { MaybeUninit::<[MaybeUninit<T>; NEW_C]>::uninit().assume_init() };
-        #[allow(clippy::needless_range_loop)] // silly paperclip, your suggestion doesn't compile
-        for i in 0..self.write {
-            // SAFETY: indexes lower than self.write are initialised
-            data[i].write(unsafe { self.take(i) });
+        // SAFETY: the last element is taken below
+        unsafe { self.set_len(len - 1) };
+        // SAFETY: len - 1 points to initialised memory,
+        // this deinitialises this memory
+        let item = unsafe { self.take(len - 1) };
+        // SAFETY: len - 1 was just moved out
+        unsafe { self.poison(len - 1, len) };
+        Some(item)
+    }
+
+    /// Pops elements from the back one at a time, last first, leaving
+    /// the vector empty if the iterator is run to the end
+    pub fn pop_iter(&mut self) -> impl Iterator<Item = T> + '_ {
+        iter::from_fn(|| self.pop())
+    }
+
+    /// Converts into an array if full, `None` otherwise (dropping the elements)
+    pub fn into_array(self) -> Option<[T; C]> {
+        self.try_into_array().ok()
+    }
+
+    /// Converts into an array
+    /// # Errors
+    /// Gives back `self` intact if it isn't full
+    pub fn try_into_array(self) -> Result<[T; C], Self> {
+        if !self.is_full() {
+            return Err(self);
         }
-        Some(ArrayVec {
-            data,
-            write: self.write,
+        let this = mem::ManuallyDrop::new(self);
+        // SAFETY: every element is initialised as the vector is full,
+        // [MaybeUninit<T>; C] has the layout of [T; C] and this isn't dropped
+        Ok(unsafe { this.as_ptr().cast::<[T; C]>().read() })
+    }
+
+    /// Moves the elements into a vector of capacity `NEW_C`,
+    /// `None` if they don't fit (dropping them)
+    pub fn resize<const NEW_C: usize>(self) -> Option<ArrayVec<T, NEW_C, L>> {
+        self.try_shrink().ok()
+    }
+
+    /// Moves the elements into a vector of a larger capacity
+    ///
+    /// This is the infallible widening conversion, `From` can't be implemented
+    /// between capacities as it would overlap `From<T> for T` when they're
+    /// equal, for the same reason [`ArrayVec::try_shrink`] isn't `TryFrom`
+    /// # Panics
+    /// At compile time if `NEW_C < C`
+    pub fn grow<const NEW_C: usize>(self) -> ArrayVec<T, NEW_C, L> {
+        let () = CapacityCheck::<C, NEW_C>::GROWS;
+        // SAFETY: len <= C <= NEW_C
+        unsafe { self.move_into() }
+    }
+
+    /// Moves the elements into a vector of capacity `NEW_C`,
+    /// giving back `self` intact if they don't fit
+    /// # Errors
+    /// If `self.len() > NEW_C`
+    pub fn try_shrink<const NEW_C: usize>(self) -> Result<ArrayVec<T, NEW_C, L>, Self> {
+        if self.len() > NEW_C {
+            return Err(self);
+        }
+        // SAFETY: len <= NEW_C checked above
+        Ok(unsafe { self.move_into() })
+    }
+
+    /// Regroups the elements into arrays of `K` in a vector of capacity `M`,
+    /// the inverse of [`ArrayVec::try_flatten`]
+    /// # Errors
+    /// Gives back `self` intact if `self.len()` isn't a multiple of `K`
+    /// or there are more than `M` chunks
+    /// # Panics
+    /// If `K == 0`
+    pub fn into_chunks<const K: usize, const M: usize>(
+        mut self,
+    ) -> Result<ArrayVec<[T; K], M, L>, Self> {
+        assert!(K != 0, "chunk size must be non zero");
+        let len = self.len();
+        if !len.is_multiple_of(K) || len / K > M {
+            return Err(self);
+        }
+        let mut ret = ArrayVec::<[T; K], M, L>::new();
+        // SAFETY: [T; len] has the layout of [[T; K]; len / K], which fits
+        // in ret. self's length is zeroed as they've been moved out
+        unsafe {
+            ptr::copy_nonoverlapping(self.as_ptr(), ret.as_mut_ptr().cast::<T>(), len);
+            self.set_len(0);
+            self.poison(0, len);
+            ret.set_len(len / K);
+        }
+        Ok(ret)
+    }
+
+    /// Splits the elements into those where `f` is true and those where it's false,
+    /// keeping their order
+    pub fn partition<F>(self, mut f: F) -> (Self, Self)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut matched = Self::new();
+        let mut rest = Self::new();
+        for item in self {
+            if f(&item) {
+                matched.push(item);
+            } else {
+                rest.push(item);
+            }
+        }
+        (matched, rest)
+    }
+
+    /// Combines the elements pairwise with `f`, stopping at the end of the
+    /// shorter vector and dropping what's left of the other
+    pub fn zip_with<U, V, F>(self, other: ArrayVec<U, C, L>, mut f: F) -> ArrayVec<V, C, L>
+    where
+        F: FnMut(T, U) -> V,
+    {
+        let mut ret = ArrayVec::new();
+        for (a, b) in self.into_iter().zip(other) {
+            ret.push(f(a, b));
+        }
+        ret
+    }
+
+    /// Splits into the first `M` elements and the rest
+    /// # Panics
+    /// If `M > self.len()`
+    pub fn split_at_const<const M: usize>(mut self) -> (ArrayVec<T, M, L>, Self) {
+        let len = self.len();
+        assert!(M <= len, "split position is {M} but length is {len}");
+        // SAFETY: M <= len and len - M <= C
+        let tail = unsafe { self.split_tail(M) };
+        // SAFETY: self.len() == M after split_tail
+        (unsafe { self.move_into() }, tail)
+    }
+
+    /// Splits into the elements before `at` and from `at` onwards
+    /// # Panics
+    /// If `at > self.len()`
+    pub fn split_into(mut self, at: usize) -> (Self, Self) {
+        let len = self.len();
+        assert!(at <= len, "split position is {at} but length is {len}");
+        // SAFETY: at <= len and len - at <= C
+        let tail = unsafe { self.split_tail(at) };
+        (self, tail)
+    }
+
+    /// Moves the elements from `at` onwards into a new vector
+    /// # Safety
+    /// - `at <= self.len()`
+    /// - `self.len() - at <= NEW_C`
+    unsafe fn split_tail<const NEW_C: usize>(&mut self, at: usize) -> ArrayVec<T, NEW_C, L> {
+        let len = self.len();
+        let mut tail = ArrayVec::new();
+        // SAFETY: at..len is initialised and fits in tail,
+        // self is shortened to at as they've been moved out
+        unsafe {
+            ptr::copy_nonoverlapping(self.as_ptr().add(at), tail.as_mut_ptr(), len - at);
+            self.set_len(at);
+            self.poison(at, len);
+            tail.set_len(len - at);
+        }
+        tail
+    }
+
+    /// Moves the elements into a vector of capacity `NEW_C`
+    /// # Safety
+    /// `self.len() <= NEW_C`
+    unsafe fn move_into<const NEW_C: usize>(mut self) -> ArrayVec<T, NEW_C, L> {
+        let len = self.len();
+        let mut ret = ArrayVec::new();
+        // SAFETY: 0..len is initialised in self and in bounds of ret,
+        // self's length is zeroed as they've been moved out
+        unsafe {
+            ptr::copy_nonoverlapping(self.as_ptr(), ret.as_mut_ptr(), len);
+            self.set_len(0);
+            self.poison(0, len);
+            ret.set_len(len);
+        }
+        ret
+    }
+
+    pub const fn as_slice(&self) -> &[T] {
+        // SAFETY: indexes lower than len are initialised
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
+
+    pub const fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: indexes lower than len are initialised
+        unsafe { slice::from_raw_parts_mut(self.as_mut_ptr(), self.len()) }
+    }
+
+    /// Overlapping windows of `K` elements as arrays
+    /// # Panics
+    /// If `K == 0`
+    pub fn array_windows<const K: usize>(
+        &self,
+    ) -> impl ExactSizeIterator<Item = &[T; K]> + DoubleEndedIterator {
+        self.as_slice().windows(K).map(|window| {
+            // SAFETY: windows yields slices of exactly K elements
+            unsafe { &*window.as_ptr().cast::<[T; K]>() }
         })
     }
 
-    pub fn as_slice(&self) -> &[T] {
-        // TODO: MaybeUninit::slice_assume_init_ref once stabilised
-        let slice = &self.data[0..self.write];
-        let len = slice.len();
-        unsafe { core::slice::from_raw_parts(slice.as_ptr().cast::<T>(), len) }
+    /// Non-overlapping chunks of `K` elements as mutable arrays,
+    /// any remainder at the end is skipped
+    /// # Panics
+    /// If `K == 0`
+    pub fn array_chunks_mut<const K: usize>(
+        &mut self,
+    ) -> impl ExactSizeIterator<Item = &mut [T; K]> + DoubleEndedIterator {
+        self.as_mut_slice().chunks_exact_mut(K).map(|chunk| {
+            // SAFETY: chunks_exact_mut yields slices of exactly K elements
+            unsafe { &mut *chunk.as_mut_ptr().cast::<[T; K]>() }
+        })
     }
 
-    pub fn as_mut_slice(&mut self) -> &mut [T] {
-        // TODO: MaybeUninit::slice_assume_init_ref once stabilised
-        let slice = &mut self.data[0..self.write];
-        let len = slice.len(); // == self.write
+    /// The range of elements whose key equals `key` in a vector sorted by
+    /// key, empty at the insertion point if there are none
+    pub fn equal_range_by_key<K, F>(&self, key: &K, mut f: F) -> ops::Range<usize>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        let start = self.partition_point(|x| f(x) < *key);
+        let end = start + self[start..].partition_point(|x| f(x) <= *key);
+        start..end
+    }
+
+    /// The uninitialised spare capacity after the elements
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        let len = self.len();
+        &mut self.data[len..]
+    }
 
-        // SAFETY: indexes lower than self.write are initialised and thats the length of the slice here
-        unsafe { core::slice::from_raw_parts_mut(slice.as_mut_ptr().cast::<T>(), len) }
+    /// Views the elements as cells so they can be mutated
+    /// while other references to them are held
+    pub fn as_cells(&mut self) -> &[Cell<T>] {
+        Cell::from_mut(self.as_mut_slice()).as_slice_of_cells()
     }
 
     pub fn iter(&self) -> slice::Iter<'_, T> {
@@ -211,44 +850,177 @@ impl<T, const C: usize> ArrayVec<T, C> {
         self.as_mut_slice().iter_mut()
     }
 
-    // forbids 0 size ArrayVec because
-    // implementation depends on C > 0
-    // and it doesn't really make sense anyway
-    const _C_NON_ZERO: () = assert!(C != 0, "ArrayVec cannot have a capacity of 0");
+    // the length has to be able to count up to the capacity
+    const _C_FITS_LEN: () = assert!(C <= L::MAX, "capacity doesn't fit in the length type");
+}
+
+impl<A, B, const C: usize, L: LenType> ArrayVec<(A, B), C, L> {
+    /// Splits the pairs into a vector of the firsts and one of the seconds
+    pub fn unzip(self) -> (ArrayVec<A, C, L>, ArrayVec<B, C, L>) {
+        let mut firsts = ArrayVec::new();
+        let mut seconds = ArrayVec::new();
+        for (a, b) in self {
+            firsts.push(a);
+            seconds.push(b);
+        }
+        (firsts, seconds)
+    }
+}
+
+impl<T, const N: usize, const C: usize, L: LenType> ArrayVec<[T; N], C, L> {
+    /// Moves the elements of every array into one vector of capacity `M`,
+    /// giving back `self` intact if they don't fit
+    /// # Errors
+    /// If `self.len() * N > M`
+    pub fn try_flatten<const M: usize>(self) -> Result<ArrayVec<T, M, L>, Self> {
+        if self.len() * N > M {
+            return Err(self);
+        }
+        // SAFETY: len * N <= M checked above
+        Ok(unsafe { self.flatten_into() })
+    }
+
+    /// Moves the elements of every array into one vector of capacity `M`
+    /// # Safety
+    /// `self.len() * N <= M`
+    unsafe fn flatten_into<const M: usize>(mut self) -> ArrayVec<T, M, L> {
+        let arrays = self.len();
+        let len = arrays * N;
+        let mut ret = ArrayVec::new();
+        // SAFETY: [[T; N]; len] has the layout of [T; len * N], which is in
+        // bounds of ret. self's length is zeroed as they've been moved out
+        unsafe {
+            ptr::copy_nonoverlapping(self.as_ptr().cast::<T>(), ret.as_mut_ptr(), len);
+            self.set_len(0);
+            self.poison(0, arrays);
+            ret.set_len(len);
+        }
+        ret
+    }
 }
 
-impl<T, const C: usize> ArrayVec<T, C>
+impl<T, const C: usize, L: LenType> ArrayVec<T, C, L>
 where
     T: Clone,
 {
     pub fn extend_from_slice(&mut self, other: &[T]) {
         self.extend(other.iter().cloned());
     }
+
+    /// Clones all of `other` onto the end, leaving the vector untouched if it doesn't fit
+    /// # Errors
+    /// If `other.len() > self.remaining_capacity()`
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), CapacityError> {
+        self.try_reserve(other.len())?;
+        self.extend_from_slice(other);
+        Ok(())
+    }
+}
+
+impl<T, const C: usize, L: LenType> ArrayVec<T, C, L>
+where
+    T: PartialEq,
+{
+    /// Removes consecutive equal elements
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Collapses runs of consecutive equal elements into the
+    /// first of each run and its length, i.e. run length encodes
+    pub fn dedup_with_count(self) -> ArrayVec<(T, usize), C, L> {
+        let mut ret = ArrayVec::<(T, usize), C, L>::new();
+        for item in self {
+            match ret.last_mut() {
+                Some((run, count)) if *run == item => *count += 1,
+                // there are never more runs than elements
+                _ => ret.push((item, 1)),
+            }
+        }
+        ret
+    }
+
+    /// Whether any two elements are equal, in O(n²)
+    pub fn contains_duplicates(&self) -> bool {
+        self.iter()
+            .enumerate()
+            .any(|(i, a)| self[i + 1..].iter().any(|b| a == b))
+    }
+}
+
+impl<T, const C: usize, L: LenType> ArrayVec<T, C, L>
+where
+    T: Ord,
+{
+    /// Sorts then removes duplicates, leaving every element unique
+    pub fn sort_unstable_dedup(&mut self) {
+        self.sort_unstable();
+        self.dedup();
+    }
+}
+
+impl<T, const C: usize, L: LenType> ArrayVec<T, C, L>
+where
+    T: fmt::Display,
+{
+    /// Formats the elements separated by `sep` without allocating,
+    /// any format options are applied to each element
+    pub fn display_join<'a>(&'a self, sep: &'a str) -> impl fmt::Display + 'a {
+        struct Join<'a, T> {
+            items: &'a [T],
+            sep: &'a str,
+        }
+
+        impl<T: fmt::Display> fmt::Display for Join<'_, T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                for (i, item) in self.items.iter().enumerate() {
+                    if i != 0 {
+                        f.write_str(self.sep)?;
+                    }
+                    item.fmt(f)?;
+                }
+                Ok(())
+            }
+        }
+
+        Join {
+            items: self.as_slice(),
+            sep,
+        }
+    }
 }
 
-impl<T, const C: usize> IntoIterator for ArrayVec<T, C> {
+impl<T, const C: usize, L: LenType> IntoIterator for ArrayVec<T, C, L> {
     type Item = T;
-    type IntoIter = core::iter::Map<
-        core::iter::Take<core::array::IntoIter<MaybeUninit<T>, C>>,
-        fn(MaybeUninit<T>) -> T,
-    >;
+    type IntoIter = IntoIter<T, C, L>;
     fn into_iter(self) -> Self::IntoIter {
-        // not actually a safe function
-        fn assume_init<T>(val: MaybeUninit<T>) -> T {
-            // SAFETY: val is from an index below self.write
-            unsafe { val.assume_init() }
-        }
-        self.data.into_iter().take(self.write - 1).map(assume_init)
+        IntoIter::new(self)
+    }
+}
+
+impl<'a, T, const C: usize, L: LenType> IntoIterator for &'a ArrayVec<T, C, L> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
-impl<T, const C: usize> iter::Extend<T> for ArrayVec<T, C> {
+impl<'a, T, const C: usize, L: LenType> IntoIterator for &'a mut ArrayVec<T, C, L> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T, const C: usize, L: LenType> iter::Extend<T> for ArrayVec<T, C, L> {
     fn extend<U: IntoIterator<Item = T>>(&mut self, iter: U) {
         iter.into_iter().for_each(|i| self.push(i));
     }
 }
 
-impl<'a, T, const C: usize> iter::Extend<&'a T> for ArrayVec<T, C>
+impl<'a, T, const C: usize, L: LenType> iter::Extend<&'a T> for ArrayVec<T, C, L>
 where
     T: Clone,
 {
@@ -257,7 +1029,7 @@ where
     }
 }
 
-impl<'a, T, const C: usize> iter::Extend<&'a [T]> for ArrayVec<T, C>
+impl<'a, T, const C: usize, L: LenType> iter::Extend<&'a [T]> for ArrayVec<T, C, L>
 where
     T: Clone,
 {
@@ -266,7 +1038,57 @@ where
     }
 }
 
-impl<T, const C: usize> iter::FromIterator<T> for ArrayVec<T, C> {
+impl<T, const C: usize, const C2: usize, L: LenType, L2: LenType> ops::Add<ArrayVec<T, C2, L2>>
+    for ArrayVec<T, C, L>
+{
+    type Output = Self;
+    /// Concatenates the vectors, see [`ArrayVec::try_append`] for a fallible version
+    /// # Panics
+    /// If `rhs` doesn't fit
+    fn add(mut self, rhs: ArrayVec<T, C2, L2>) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl<T, const C: usize, const C2: usize, L: LenType, L2: LenType>
+    ops::AddAssign<ArrayVec<T, C2, L2>> for ArrayVec<T, C, L>
+{
+    /// Appends `rhs`, see [`ArrayVec::try_append`] for a fallible version
+    /// # Panics
+    /// If `rhs` doesn't fit
+    fn add_assign(&mut self, mut rhs: ArrayVec<T, C2, L2>) {
+        self.append(&mut rhs);
+    }
+}
+
+impl<T, const C: usize, L: LenType> ops::Add<&[T]> for ArrayVec<T, C, L>
+where
+    T: Clone,
+{
+    type Output = Self;
+    /// Concatenates clones of `rhs`, see [`ArrayVec::try_extend_from_slice`] for a fallible version
+    /// # Panics
+    /// If `rhs` doesn't fit
+    fn add(mut self, rhs: &[T]) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl<T, const C: usize, L: LenType> ops::AddAssign<&[T]> for ArrayVec<T, C, L>
+where
+    T: Clone,
+{
+    /// Appends clones of `rhs`, see [`ArrayVec::try_extend_from_slice`] for a fallible version
+    /// # Panics
+    /// If `rhs` doesn't fit
+    fn add_assign(&mut self, rhs: &[T]) {
+        assert!(self.try_extend_from_slice(rhs).is_ok(), "stackvec full");
+    }
+}
+
+impl<T, const C: usize, L: LenType> iter::FromIterator<T> for ArrayVec<T, C, L> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut ret = ArrayVec::new();
         ret.extend(iter);
@@ -274,84 +1096,230 @@ impl<T, const C: usize> iter::FromIterator<T> for ArrayVec<T, C> {
     }
 }
 
-impl<T, const C: usize> Clone for ArrayVec<T, C>
+impl<T, const C: usize, L: LenType> Clone for ArrayVec<T, C, L>
 where
     T: Clone,
 {
     fn clone(&self) -> Self {
-        // SAFETY: [MaybeUninit<T>] doesn't need to be initialised
-        let mut data: [MaybeUninit<T>; C] = unsafe { MaybeUninit::uninit().assume_init() };
-        for i in 0..self.write {
-            data[i].write(self[i].clone());
+        let mut ret = Self::new();
+        for (i, item) in self.iter().enumerate() {
+            // SAFETY: i < self.len() <= C
+            unsafe { ret.as_mut_ptr().add(i).write(item.clone()) };
+            // SAFETY: i was just written
+            unsafe { ret.set_len(i + 1) };
         }
+        ret
+    }
+}
+
+/// Zeroes the length while elements are removed from the middle,
+/// then shifts the unprocessed tail down over the holes and restores
+/// the length when dropped, even if a callback or Drop panics
+struct Compactor<'a, T, const C: usize, L: LenType> {
+    vec: &'a mut ArrayVec<T, C, L>,
+    len: usize,
+    processed: usize,
+    deleted: usize,
+}
+
+impl<'a, T, const C: usize, L: LenType> Compactor<'a, T, C, L> {
+    fn new(vec: &'a mut ArrayVec<T, C, L>) -> Self {
+        let len = vec.len();
+        // SAFETY: restored when dropped once the holes are filled
+        unsafe { vec.set_len(0) };
         Self {
-            data,
-            write: self.write,
+            vec,
+            len,
+            processed: 0,
+            deleted: 0,
+        }
+    }
+
+    /// Pointer to the next unprocessed element
+    /// # Safety
+    /// `self.processed < self.len`
+    unsafe fn current(&mut self) -> *mut T {
+        // SAFETY: processed < len <= C
+        unsafe { self.vec.as_mut_ptr().add(self.processed) }
+    }
+
+    /// Moves the current element down over the holes
+    /// # Safety
+    /// `self.processed < self.len`
+    unsafe fn keep(&mut self) {
+        if self.deleted > 0 {
+            // SAFETY: current is initialised, the hole deleted
+            // places below it is deinitialised
+            unsafe {
+                let cur = self.current();
+                ptr::copy_nonoverlapping(cur, cur.sub(self.deleted), 1);
+            }
+        }
+        self.processed += 1;
+    }
+
+    /// Moves the current element out
+    /// # Safety
+    /// `self.processed < self.len`
+    unsafe fn take(&mut self) -> T {
+        // SAFETY: upheld by the caller
+        let cur = unsafe { self.current() };
+        self.processed += 1;
+        self.deleted += 1;
+        // SAFETY: cur is initialised and now counted as deleted
+        unsafe { cur.read() }
+    }
+
+    /// Drops the current element
+    /// # Safety
+    /// `self.processed < self.len`
+    unsafe fn delete(&mut self) {
+        // SAFETY: upheld by the caller
+        let cur = unsafe { self.current() };
+        // counted before dropping so a panicking Drop
+        // isn't dropped again by the guard
+        self.processed += 1;
+        self.deleted += 1;
+        // SAFETY: cur is initialised and now counted as deleted
+        unsafe { ptr::drop_in_place(cur) };
+    }
+
+    /// Writes `item` into the first hole, keeping it in place of a taken element
+    /// # Safety
+    /// An element has been taken since the last `keep` or `put`
+    unsafe fn put(&mut self, item: T) {
+        // SAFETY: after a take the slot just past the packed
+        // kept elements is a hole below processed
+        unsafe {
+            self.vec
+                .as_mut_ptr()
+                .add(self.processed - self.deleted)
+                .write(item);
+        }
+        self.deleted -= 1;
+    }
+}
+
+impl<T, const C: usize, L: LenType> Drop for Compactor<'_, T, C, L> {
+    fn drop(&mut self) {
+        let ptr = self.vec.as_mut_ptr();
+        // SAFETY: processed..len is untouched and initialised,
+        // the kept elements before it are packed below processed - deleted
+        unsafe {
+            ptr::copy(
+                ptr.add(self.processed),
+                ptr.add(self.processed - self.deleted),
+                self.len - self.processed,
+            );
+            self.vec.set_len(self.len - self.deleted);
+            self.vec.poison(self.len - self.deleted, self.len);
         }
+        #[cfg(feature = "debug-invariants")]
+        self.vec.assert_invariants();
+    }
+}
+
+/// Poisons `from..to` when dropped, so slots are poisoned
+/// even if dropping what was in them panics
+struct PoisonGuard<'a, T, const C: usize, L: LenType> {
+    vec: &'a mut ArrayVec<T, C, L>,
+    from: usize,
+    to: usize,
+}
+
+impl<T, const C: usize, L: LenType> Drop for PoisonGuard<'_, T, C, L> {
+    fn drop(&mut self) {
+        // SAFETY: whoever made the guard has vacated from..to by the time it drops
+        unsafe { self.vec.poison(self.from, self.to) };
+    }
+}
+
+/// Compile time assertions between two capacities
+struct CapacityCheck<const C: usize, const NEW_C: usize>;
+
+impl<const C: usize, const NEW_C: usize> CapacityCheck<C, NEW_C> {
+    const GROWS: () = assert!(NEW_C >= C, "grow can't lower the capacity");
+}
+
+impl<T, const C: usize, L: LenType> Drop for ArrayVec<T, C, L> {
+    fn drop(&mut self) {
+        self.clear();
     }
 }
 
 // -------------------- trivial impls -------------------- \\
 
-impl<T, const C: usize> ops::Deref for ArrayVec<T, C> {
+impl<T, const C: usize, L: LenType> ops::Deref for ArrayVec<T, C, L> {
     type Target = [T];
     fn deref(&self) -> &Self::Target {
         self.as_slice()
     }
 }
 
-impl<T, const C: usize> ops::DerefMut for ArrayVec<T, C> {
+impl<T, const C: usize, L: LenType> ops::DerefMut for ArrayVec<T, C, L> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_mut_slice()
     }
 }
 
-impl<T, const C: usize> fmt::Debug for ArrayVec<T, C>
+impl<T, const C: usize, L: LenType> fmt::Debug for ArrayVec<T, C, L>
 where
     T: fmt::Debug,
 {
+    /// `{:?}` formats like a slice, `{:#?}` also shows the
+    /// element type, capacity, length type, length and spare capacity
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write!(
+                f,
+                "ArrayVec<{}, {C}, {}> (len {}, spare {}) ",
+                core::any::type_name::<T>(),
+                core::any::type_name::<L>(),
+                self.len(),
+                self.remaining_capacity()
+            )?;
+        }
         self.as_slice().fmt(f)
     }
 }
 
-impl<T, const C: usize> Default for ArrayVec<T, C> {
+impl<T, const C: usize, L: LenType> Default for ArrayVec<T, C, L> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T, const C: usize> AsMut<[T]> for ArrayVec<T, C> {
+impl<T, const C: usize, L: LenType> AsMut<[T]> for ArrayVec<T, C, L> {
     fn as_mut(&mut self) -> &mut [T] {
         self
     }
 }
 
-impl<T, const C: usize> AsMut<ArrayVec<T, C>> for ArrayVec<T, C> {
-    fn as_mut(&mut self) -> &mut ArrayVec<T, C> {
+impl<T, const C: usize, L: LenType> AsMut<ArrayVec<T, C, L>> for ArrayVec<T, C, L> {
+    fn as_mut(&mut self) -> &mut ArrayVec<T, C, L> {
         self
     }
 }
 
-impl<T, const C: usize> AsRef<[T]> for ArrayVec<T, C> {
+impl<T, const C: usize, L: LenType> AsRef<[T]> for ArrayVec<T, C, L> {
     fn as_ref(&self) -> &[T] {
         self
     }
 }
 
-impl<T, const C: usize> AsRef<ArrayVec<T, C>> for ArrayVec<T, C> {
-    fn as_ref(&self) -> &ArrayVec<T, C> {
+impl<T, const C: usize, L: LenType> AsRef<ArrayVec<T, C, L>> for ArrayVec<T, C, L> {
+    fn as_ref(&self) -> &ArrayVec<T, C, L> {
         self
     }
 }
 
-impl<T, const C: usize> core::borrow::Borrow<[T]> for ArrayVec<T, C> {
+impl<T, const C: usize, L: LenType> core::borrow::Borrow<[T]> for ArrayVec<T, C, L> {
     fn borrow(&self) -> &[T] {
         self.as_slice()
     }
 }
 
-impl<T, const C: usize> core::borrow::BorrowMut<[T]> for ArrayVec<T, C> {
+impl<T, const C: usize, L: LenType> core::borrow::BorrowMut<[T]> for ArrayVec<T, C, L> {
     fn borrow_mut(&mut self) -> &mut [T] {
         self.as_mut_slice()
     }
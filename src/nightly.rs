@@ -0,0 +1,26 @@
+use crate::{ArrayVec, LenType};
+
+impl<T, const C: usize, L: LenType> ArrayVec<T, C, L> {
+    /// Appends the elements of `other` in a vector with the capacity of both
+    /// so it can never overflow
+    pub fn concat<const C2: usize>(self, other: ArrayVec<T, C2, L>) -> ArrayVec<T, { C + C2 }, L>
+    where
+        [(); C + C2]:,
+    {
+        let mut ret = self.grow();
+        ret.extend(other);
+        ret
+    }
+}
+
+impl<T, const N: usize, const C: usize, L: LenType> ArrayVec<[T; N], C, L> {
+    /// Moves the elements of every array into one vector,
+    /// sized so they always fit
+    pub fn flatten(self) -> ArrayVec<T, { N * C }, L>
+    where
+        [(); N * C]:,
+    {
+        // SAFETY: len <= C so len * N <= N * C
+        unsafe { self.flatten_into() }
+    }
+}
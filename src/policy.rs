@@ -0,0 +1,168 @@
+//! What pushing onto a full vector does
+//!
+//! [`PolicyVec`] wraps an [`ArrayVec`] and replaces `push` with one that
+//! defers to an [`OverflowPolicy`] when full: [`Panic`] like
+//! [`ArrayVec::push`], [`Saturate`] to discard the new element or
+//! [`Overwrite`] to evict the oldest one
+
+use core::{fmt, marker::PhantomData, ops};
+
+use crate::{ArrayVec, LenType};
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::Panic {}
+    impl Sealed for super::Saturate {}
+    impl Sealed for super::Overwrite {}
+}
+
+/// Decides what [`PolicyVec::push`] does when the vector is full,
+/// implemented for [`Panic`], [`Saturate`] and [`Overwrite`], this trait is sealed
+pub trait OverflowPolicy: sealed::Sealed {
+    /// Handles pushing `item` onto a full `vec`, returning the element discarded
+    fn push_full<T, const C: usize, L: LenType>(vec: &mut ArrayVec<T, C, L>, item: T) -> Option<T>;
+}
+
+/// Panics when pushing onto a full vector
+#[derive(Debug, Clone, Copy)]
+pub struct Panic;
+
+/// Discards the new element when pushing onto a full vector
+#[derive(Debug, Clone, Copy)]
+pub struct Saturate;
+
+/// Evicts the oldest element when pushing onto a full vector, shifting the
+/// rest down so it's O(C)
+#[derive(Debug, Clone, Copy)]
+pub struct Overwrite;
+
+impl OverflowPolicy for Panic {
+    fn push_full<T, const C: usize, L: LenType>(vec: &mut ArrayVec<T, C, L>, item: T) -> Option<T> {
+        vec.push(item);
+        None
+    }
+}
+
+impl OverflowPolicy for Saturate {
+    fn push_full<T, const C: usize, L: LenType>(_: &mut ArrayVec<T, C, L>, item: T) -> Option<T> {
+        Some(item)
+    }
+}
+
+impl OverflowPolicy for Overwrite {
+    fn push_full<T, const C: usize, L: LenType>(vec: &mut ArrayVec<T, C, L>, item: T) -> Option<T> {
+        if C == 0 {
+            return Some(item);
+        }
+        let oldest = vec.remove(0);
+        vec.push(item);
+        Some(oldest)
+    }
+}
+
+/// [`ArrayVec`] whose `push` follows the [`OverflowPolicy`] `P` when full
+///
+/// Derefs to the inner `ArrayVec` for reading, only the methods here can
+/// modify it so every element added goes through the policy
+pub struct PolicyVec<T, const C: usize, P: OverflowPolicy, L: LenType = usize> {
+    inner: ArrayVec<T, C, L>,
+    policy: PhantomData<P>,
+}
+
+impl<T, const C: usize, P: OverflowPolicy, L: LenType> PolicyVec<T, C, P, L> {
+    /// Creates a new empty `PolicyVec`
+    /// # Panics
+    /// At compile time if `C` doesn't fit in `L`
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::from_inner(ArrayVec::new())
+    }
+
+    /// Wraps the elements in a `PolicyVec`
+    pub const fn from_inner(inner: ArrayVec<T, C, L>) -> Self {
+        Self {
+            inner,
+            policy: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> ArrayVec<T, C, L> {
+        self.inner
+    }
+
+    /// Pushes `item` to the end, handing it to `P` if full
+    /// and returning whichever element was discarded
+    /// # Panics
+    /// If full and `P` is [`Panic`]
+    pub fn push(&mut self, item: T) -> Option<T> {
+        match self.inner.try_push(item) {
+            Ok(()) => None,
+            Err(err) => P::push_full(&mut self.inner, err.element()),
+        }
+    }
+
+    /// Removes and returns the last element
+    pub fn pop(&mut self) -> Option<T> {
+        self.inner.pop()
+    }
+
+    /// Removes and returns the element at `index`, shifting the rest down
+    /// # Panics
+    /// If `index` is out of bounds
+    pub fn remove(&mut self, index: usize) -> T {
+        self.inner.remove(index)
+    }
+
+    /// Drops the elements past `len`
+    pub fn truncate(&mut self, len: usize) {
+        self.inner.truncate(len);
+    }
+
+    /// Drops every element
+    pub fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.inner
+    }
+}
+
+impl<T, const C: usize, P: OverflowPolicy, L: LenType> ops::Deref for PolicyVec<T, C, P, L> {
+    type Target = ArrayVec<T, C, L>;
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T, const C: usize, P: OverflowPolicy, L: LenType> Extend<T> for PolicyVec<T, C, P, L> {
+    /// Pushes every element, following `P` once full
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T, const C: usize, P: OverflowPolicy, L: LenType> fmt::Debug for PolicyVec<T, C, P, L>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl<T, const C: usize, P: OverflowPolicy, L: LenType> Default for PolicyVec<T, C, P, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const C: usize, P: OverflowPolicy, L: LenType> From<ArrayVec<T, C, L>>
+    for PolicyVec<T, C, P, L>
+{
+    fn from(inner: ArrayVec<T, C, L>) -> Self {
+        Self::from_inner(inner)
+    }
+}
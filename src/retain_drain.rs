@@ -0,0 +1,71 @@
+use core::{fmt, iter::FusedIterator};
+
+use crate::{ArrayVec, Compactor, LenType};
+
+/// Iterator over the elements removed by [`ArrayVec::retain_drain`]
+///
+/// Elements not yet visited when this is dropped are kept
+pub struct RetainDrain<'a, T, const C: usize, L: LenType, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    compactor: Compactor<'a, T, C, L>,
+    pred: F,
+}
+
+impl<'a, T, const C: usize, L: LenType, F> RetainDrain<'a, T, C, L, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    pub(crate) fn new(vec: &'a mut ArrayVec<T, C, L>, pred: F) -> Self {
+        Self {
+            compactor: Compactor::new(vec),
+            pred,
+        }
+    }
+}
+
+impl<T, const C: usize, L: LenType, F> Iterator for RetainDrain<'_, T, C, L, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    type Item = T;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.compactor.processed < self.compactor.len {
+            // SAFETY: processed < len
+            let cur = unsafe { self.compactor.current() };
+            // SAFETY: cur is initialised
+            if (self.pred)(unsafe { &*cur }) {
+                // SAFETY: processed < len
+                unsafe { self.compactor.keep() };
+            } else {
+                // SAFETY: processed < len
+                return Some(unsafe { self.compactor.take() });
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.compactor.len - self.compactor.processed))
+    }
+}
+
+impl<T, const C: usize, L: LenType, F> FusedIterator for RetainDrain<'_, T, C, L, F> where
+    F: FnMut(&T) -> bool
+{
+}
+
+impl<T, const C: usize, L: LenType, F> fmt::Debug for RetainDrain<'_, T, C, L, F>
+where
+    F: FnMut(&T) -> bool,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetainDrain")
+            .field(
+                "remaining",
+                &(self.compactor.len - self.compactor.processed),
+            )
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,133 @@
+use core::{
+    mem::{self, MaybeUninit},
+    ptr,
+};
+
+use crate::{ArrayVec, LenType};
+
+impl<T, const C: usize, L: LenType> ArrayVec<T, C, L>
+where
+    T: Ord,
+{
+    /// Sorts keeping equal elements in order, with a merge sort
+    /// that uses `scratch` instead of allocating
+    /// # Panics
+    /// If `scratch.len() < self.len() / 2`
+    pub fn sort_stable_with_scratch(&mut self, scratch: &mut [MaybeUninit<T>]) {
+        let len = self.len();
+        assert!(
+            scratch.len() >= len / 2,
+            "scratch length is {} but needs to be at least {}",
+            scratch.len(),
+            len / 2
+        );
+        // SAFETY: 0..len is initialised and scratch holds len / 2
+        unsafe {
+            merge_sort(
+                self.as_mut_ptr(),
+                len,
+                scratch.as_mut_ptr().cast::<T>(),
+                &mut T::lt,
+            );
+        }
+    }
+
+    /// Sorts keeping equal elements in order, with a merge sort using the
+    /// spare capacity as scratch when there's enough and the stack otherwise
+    pub fn sort_stable(&mut self) {
+        let len = self.len();
+        if self.remaining_capacity() < len / 2 {
+            self.sort_stable_on_stack();
+            return;
+        }
+        let ptr = self.as_mut_ptr();
+        // SAFETY: 0..len is initialised and len..C is spare and holds len / 2
+        unsafe { merge_sort(ptr, len, ptr.add(len), &mut T::lt) };
+    }
+
+    // separate so the scratch array only takes up stack when it's needed
+    #[inline(never)]
+    fn sort_stable_on_stack(&mut self) {
+        let mut scratch = ArrayVec::<T, C, L>::new();
+        self.sort_stable_with_scratch(scratch.spare_capacity_mut());
+    }
+}
+
+/// Merge sorts `len` elements from `v`
+/// # Safety
+/// - `v` points to `len` initialised elements
+/// - `buf` is valid for writes of `len / 2` elements and doesn't overlap `v`
+unsafe fn merge_sort<T, F>(v: *mut T, len: usize, buf: *mut T, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    // zero sized elements are indistinguishable so already sorted, and
+    // the pointer arithmetic in merge can't tell them apart either
+    if len <= 1 || mem::size_of::<T>() == 0 {
+        return;
+    }
+    let mid = len / 2;
+    // SAFETY: both halves are initialised and need at most len / 4 scratch
+    unsafe {
+        merge_sort(v, mid, buf, is_less);
+        merge_sort(v.add(mid), len - mid, buf, is_less);
+        merge(v, mid, len, buf, is_less);
+    }
+}
+
+/// Merges the sorted runs `0..mid` and `mid..len` of `v`
+/// # Safety
+/// - `v` points to `len` initialised elements
+/// - `buf` is valid for writes of `mid` elements and doesn't overlap `v`
+unsafe fn merge<T, F>(v: *mut T, mid: usize, len: usize, buf: *mut T, is_less: &mut F)
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    // copies what's left of the left run into place when dropped, so every
+    // element is back in `v` exactly once even if `is_less` panics
+    struct Hole<T> {
+        start: *mut T,
+        end: *mut T,
+        dest: *mut T,
+    }
+
+    impl<T> Drop for Hole<T> {
+        fn drop(&mut self) {
+            // SAFETY: start..end are the unmerged left elements in buf,
+            // the same number of slots before the right run are free
+            unsafe {
+                let remaining = self.end.offset_from(self.start).unsigned_abs();
+                ptr::copy_nonoverlapping(self.start, self.dest, remaining);
+            }
+        }
+    }
+
+    // SAFETY: mid <= len / 2 so the left run fits in buf
+    let mut hole = unsafe {
+        ptr::copy_nonoverlapping(v, buf, mid);
+        Hole {
+            start: buf,
+            end: buf.add(mid),
+            dest: v,
+        }
+    };
+    // SAFETY: v + len is one past the end
+    let (mut right, right_end) = unsafe { (v.add(mid), v.add(len)) };
+    while hole.start < hole.end && right < right_end {
+        // only taking from the right when strictly less keeps it stable
+        // SAFETY: both are initialised
+        let take_right = is_less(unsafe { &*right }, unsafe { &*hole.start });
+        let src = if take_right { right } else { hole.start };
+        // SAFETY: dest is always below right as fewer than mid
+        // elements from the left have been merged
+        unsafe {
+            ptr::copy_nonoverlapping(src, hole.dest, 1);
+            hole.dest = hole.dest.add(1);
+            if take_right {
+                right = right.add(1);
+            } else {
+                hole.start = hole.start.add(1);
+            }
+        }
+    }
+}
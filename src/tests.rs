@@ -1,4 +1,12 @@
-use crate::ArrayVec;
+extern crate std;
+
+use core::cell::Cell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::{
+    Align64, AlignedArrayVec, ArrayGapBuffer, ArrayGrid, ArrayVec, BinaryHeapView, ByteQueue,
+    CapacityError, ExtendExactError, FixedCapacityVec, TryExtend,
+};
 
 fn init_stack_full() -> ArrayVec<i32, 5> {
     let mut nums = ArrayVec::new();
@@ -51,6 +59,9 @@ fn insert_nums() {
     let mut nums = init_stack_half_full();
     nums.insert(1, 14);
     assert_eq!(*nums, [1, 14, 2, 3]);
+    nums.insert(4, 15);
+    assert_eq!(*nums, [1, 14, 2, 3, 15]);
+    assert!(catch_unwind(AssertUnwindSafe(|| nums.insert(0, 0))).is_err());
 }
 
 #[test]
@@ -69,6 +80,7 @@ fn len_nums() {
 }
 
 #[test]
+#[allow(clippy::get_first)]
 fn get_nums() {
     let mut nums = init_stack_half_full();
     assert_eq!(nums.get(0), Some(&1));
@@ -98,7 +110,7 @@ struct TestDrop<'a> {
     has_dropped: &'a mut bool,
 }
 
-impl<'a> Drop for TestDrop<'a> {
+impl Drop for TestDrop<'_> {
     fn drop(&mut self) {
         assert!(!*self.has_dropped, "double free");
         *self.has_dropped = true;
@@ -118,3 +130,1256 @@ fn drop_working() {
     drop(array);
     assert!(has_dropped);
 }
+
+#[test]
+fn len_type_size() {
+    use core::mem::size_of;
+    assert_eq!(size_of::<ArrayVec<u8, 8, u8>>(), 9);
+    assert_eq!(size_of::<ArrayVec<u8, 6, u16>>(), 8);
+    assert_eq!(size_of::<ArrayVec<u8, 8>>(), 8 + size_of::<usize>());
+}
+
+#[test]
+fn len_type_nums() {
+    let mut nums = ArrayVec::<u32, 300, u16>::new();
+    nums.extend(0..300);
+    assert!(nums.is_full());
+    assert_eq!(nums.len(), 300);
+    assert_eq!(nums.pop(), Some(299));
+    nums.remove(0);
+    assert_eq!(nums.len(), 298);
+    assert_eq!(nums[0], 1);
+}
+
+#[repr(C)]
+struct ForeignVec {
+    data: [u16; 4],
+    len: u32,
+}
+
+#[test]
+fn repr_c_layout() {
+    let mut foreign = ForeignVec {
+        data: [7, 8, 9, 0],
+        len: 3,
+    };
+    assert_eq!(
+        core::mem::size_of::<ForeignVec>(),
+        core::mem::size_of::<ArrayVec<u16, 4, u32>>()
+    );
+    let ptr = core::ptr::addr_of_mut!(foreign).cast::<ArrayVec<u16, 4, u32>>();
+    // SAFETY: ForeignVec has the same layout and 3 initialised elements
+    let nums = unsafe { ArrayVec::from_mut_ptr(ptr) };
+    assert_eq!(**nums, [7, 8, 9]);
+    nums.push(10);
+    nums[0] = 1;
+    assert_eq!(foreign.len, 4);
+    assert_eq!(foreign.data, [1, 8, 9, 10]);
+}
+
+#[test]
+fn zst_nums() {
+    let mut units = ArrayVec::<(), 8>::new();
+    units.extend(core::iter::repeat_n((), 5));
+    assert_eq!(units.len(), 5);
+    units.insert(2, ());
+    assert_eq!(units.remove(0), ());
+    assert_eq!(units.swap_remove(1), ());
+    units.retain(|()| false);
+    assert!(units.is_empty());
+    assert_eq!(units.pop(), None);
+    units.extend(core::iter::repeat_n((), 8));
+    assert!(units.is_full());
+    assert_eq!(units.iter().count(), 8);
+    assert_eq!(units.into_array(), Some([(); 8]));
+}
+
+static ZST_DROPS: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
+
+struct ZstDrop;
+
+impl Drop for ZstDrop {
+    fn drop(&mut self) {
+        ZST_DROPS.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn zst_drops() {
+    let mut zsts = ArrayVec::<ZstDrop, 4>::new();
+    (0..3).for_each(|_| zsts.push(ZstDrop));
+    zsts.insert(1, ZstDrop);
+    assert_eq!(ZST_DROPS.load(core::sync::atomic::Ordering::Relaxed), 0);
+    drop(zsts.remove(2));
+    assert_eq!(ZST_DROPS.load(core::sync::atomic::Ordering::Relaxed), 1);
+    zsts.clear();
+    assert_eq!(ZST_DROPS.load(core::sync::atomic::Ordering::Relaxed), 4);
+}
+
+/// Counts its drops and panics when dropped if `panics` is set
+struct PanicDrop<'a> {
+    drops: &'a Cell<usize>,
+    panics: bool,
+}
+
+impl Drop for PanicDrop<'_> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+        assert!(!self.panics, "PanicDrop panicked");
+    }
+}
+
+fn init_panic_drops(drops: &Cell<usize>, panic_at: usize) -> ArrayVec<PanicDrop<'_>, 5> {
+    (0..5)
+        .map(|i| PanicDrop {
+            drops,
+            panics: i == panic_at,
+        })
+        .collect()
+}
+
+/// Counts comparisons in `cmps`, panicking on the `panic_at`th
+#[derive(PartialEq, Eq)]
+struct PanicCmp<'a> {
+    value: u8,
+    cmps: &'a Cell<usize>,
+    panic_at: usize,
+}
+
+impl PartialOrd for PanicCmp<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PanicCmp<'_> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.cmps.set(self.cmps.get() + 1);
+        assert!(self.cmps.get() < self.panic_at, "PanicCmp panicked");
+        self.value.cmp(&other.value)
+    }
+}
+
+#[test]
+fn drop_all_elements() {
+    let drops = Cell::new(0);
+    let vec = init_panic_drops(&drops, usize::MAX);
+    drop(vec);
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn clear_panicking_drop() {
+    let drops = Cell::new(0);
+    let mut vec = init_panic_drops(&drops, 2);
+    assert!(catch_unwind(AssertUnwindSafe(|| vec.clear())).is_err());
+    assert!(vec.is_empty());
+    assert_eq!(drops.get(), 5);
+    drop(vec);
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn retain_panicking_predicate() {
+    let mut nums = ArrayVec::<_, 10>::new();
+    nums.extend(0..10);
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        nums.retain(|&i| {
+            assert!(i != 5, "predicate panicked");
+            i % 2 == 0
+        });
+    }));
+    assert!(res.is_err());
+    assert_eq!(*nums, [0, 2, 4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+fn retain_panicking_drop() {
+    let drops = Cell::new(0);
+    let mut vec = init_panic_drops(&drops, 1);
+    let res = catch_unwind(AssertUnwindSafe(|| vec.retain(|_| false)));
+    assert!(res.is_err());
+    assert_eq!(drops.get(), 2);
+    assert_eq!(vec.len(), 3);
+    drop(vec);
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn into_iter_nums() {
+    let nums = init_stack_full();
+    let mut iter = nums.into_iter();
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(5));
+    assert_eq!(iter.as_slice(), &[2, 3, 4]);
+    assert_eq!(iter.collect::<ArrayVec<_, 3>>().as_slice(), &[2, 3, 4]);
+    assert_eq!(ArrayVec::<i32, 2>::new().into_iter().next(), None);
+}
+
+#[test]
+fn into_iter_drops_rest() {
+    let drops = Cell::new(0);
+    let mut iter = init_panic_drops(&drops, usize::MAX).into_iter();
+    drop(iter.next());
+    assert_eq!(drops.get(), 1);
+    drop(iter);
+    assert_eq!(drops.get(), 5);
+}
+
+static EVENTS: std::sync::Mutex<ArrayVec<u8, 4>> = std::sync::Mutex::new(ArrayVec::NEW);
+
+#[test]
+fn const_new() {
+    const EMPTY: ArrayVec<u8, 4, u8> = ArrayVec::new();
+    assert!(EMPTY.is_empty());
+    let mut events = EVENTS.lock().unwrap();
+    events.push(1);
+    assert_eq!(events.as_slice(), &[1]);
+}
+
+const fn squares() -> ArrayVec<u32, 8> {
+    let mut nums = ArrayVec::new();
+    let mut i = 0;
+    while !nums.is_full() {
+        nums.push(i * i);
+        i += 1;
+    }
+    let _ = nums.pop();
+    nums
+}
+
+static SQUARES: ArrayVec<u32, 8> = squares();
+
+#[test]
+fn const_push_pop() {
+    assert_eq!(SQUARES.as_slice(), &[0, 1, 4, 9, 16, 25, 36]);
+    assert_eq!(SQUARES.len(), 7);
+}
+
+#[test]
+fn zero_capacity() {
+    let mut empty = ArrayVec::<i32, 0>::new();
+    assert!(empty.is_empty());
+    assert!(empty.is_full());
+    assert_eq!(empty.capacity(), 0);
+    assert_eq!(empty.pop(), None);
+    assert_eq!(empty.iter().next(), None);
+    assert!(catch_unwind(AssertUnwindSafe(|| empty.push(1))).is_err());
+    empty.retain(|_| true);
+    empty.clear();
+    assert_eq!(empty.clone().into_iter().next(), None);
+    assert_eq!(empty.into_array(), Some([]));
+}
+
+#[test]
+fn grow_and_shrink() {
+    let nums = init_stack_half_full();
+    let mut grown = nums.grow::<8>();
+    assert_eq!(grown.capacity(), 8);
+    grown.extend(4..=8);
+    let grown = grown.try_shrink::<5>().unwrap_err();
+    assert_eq!(*grown, [1, 2, 3, 4, 5, 6, 7, 8]);
+    let shrunk = init_stack_half_full().try_shrink::<3>().unwrap();
+    assert!(shrunk.is_full());
+    assert_eq!(*shrunk, [1, 2, 3]);
+    assert!(init_stack_full().resize::<4>().is_none());
+}
+
+#[test]
+#[cfg(feature = "nightly")]
+fn concat_nums() {
+    let nums = init_stack_full().concat(init_stack_half_full());
+    assert_eq!(nums.capacity(), 10);
+    assert_eq!(*nums, [1, 2, 3, 4, 5, 1, 2, 3]);
+}
+
+#[test]
+fn flatten_arrays() {
+    let mut chunks = ArrayVec::<[u8; 2], 4>::new();
+    chunks.extend([[1, 2], [3, 4], [5, 6]]);
+    let chunks = chunks.try_flatten::<5>().unwrap_err();
+    let flat = chunks.try_flatten::<6>().unwrap();
+    assert!(flat.is_full());
+    assert_eq!(*flat, [1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+#[cfg(feature = "nightly")]
+fn flatten_arrays_nightly() {
+    let mut chunks = ArrayVec::<[u8; 2], 4>::new();
+    chunks.extend([[1, 2], [3, 4]]);
+    let flat = chunks.flatten();
+    assert_eq!(flat.capacity(), 8);
+    assert_eq!(*flat, [1, 2, 3, 4]);
+}
+
+#[test]
+fn split_nums() {
+    let (head, tail) = init_stack_full().split_at_const::<2>();
+    assert_eq!(head.capacity(), 2);
+    assert_eq!(*head, [1, 2]);
+    assert_eq!(*tail, [3, 4, 5]);
+    let (head, tail) = init_stack_half_full().split_into(3);
+    assert_eq!(*head, [1, 2, 3]);
+    assert!(tail.is_empty());
+    let res = catch_unwind(|| init_stack_half_full().split_at_const::<4>());
+    assert!(res.is_err());
+}
+
+#[test]
+fn chunk_nums() {
+    let mut samples = ArrayVec::<f32, 8>::new();
+    samples.extend([0.0, 1.0, 2.0, 3.0, 4.0]);
+    let mut samples = samples.into_chunks::<2, 4>().unwrap_err();
+    samples.push(5.0);
+    let samples = samples.into_chunks::<2, 2>().unwrap_err();
+    let frames = samples.into_chunks::<2, 3>().unwrap();
+    assert_eq!(*frames, [[0.0, 1.0], [2.0, 3.0], [4.0, 5.0]]);
+    let samples = frames.try_flatten::<6>().unwrap();
+    assert_eq!(*samples, [0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+}
+
+#[test]
+fn display_join_nums() {
+    let nums = init_stack_half_full();
+    assert_eq!(std::format!("{}", nums.display_join(", ")), "1, 2, 3");
+    assert_eq!(std::format!("{:02}", nums.display_join("-")), "01-02-03");
+    let empty = ArrayVec::<i32, 2>::new();
+    assert_eq!(std::format!("{}", empty.display_join(", ")), "");
+}
+
+#[test]
+fn debug_nums() {
+    let nums = init_stack_half_full();
+    assert_eq!(std::format!("{nums:?}"), "[1, 2, 3]");
+    assert_eq!(
+        std::format!("{nums:#?}"),
+        "ArrayVec<i32, 5, usize> (len 3, spare 2) [\n    1,\n    2,\n    3,\n]"
+    );
+    let small: ArrayVec<u8, 2, u8> = ArrayVec::from_iter([7]);
+    assert_eq!(
+        std::format!("{small:#?}"),
+        "ArrayVec<u8, 2, u8> (len 1, spare 1) [\n    7,\n]"
+    );
+}
+
+#[test]
+fn try_push_nums() {
+    let mut nums = init_stack_half_full();
+    assert_eq!(nums.try_push(4), Ok(()));
+    assert_eq!(nums.try_push(5), Ok(()));
+    let err = nums.try_push(6).unwrap_err();
+    assert_eq!(err, CapacityError::new(6));
+    assert_eq!(err.element(), 6);
+    assert_eq!(std::format!("{err}"), "insufficient capacity");
+}
+
+fn fill_generic<V: FixedCapacityVec<i32>>(vec: &mut V) -> usize {
+    let mut pushed = 0;
+    while vec.try_push(7).is_ok() {
+        pushed += 1;
+    }
+    pushed
+}
+
+#[test]
+fn fixed_capacity_trait() {
+    let mut nums = init_stack_half_full();
+    assert_eq!(fill_generic(&mut nums), 2);
+    assert!(FixedCapacityVec::is_full(&nums));
+    assert_eq!(FixedCapacityVec::pop(&mut nums), Some(7));
+    assert_eq!(FixedCapacityVec::as_slice(&nums), &[1, 2, 3, 7]);
+
+    let mut aligned = AlignedArrayVec::<i32, 3, Align64>::new();
+    assert_eq!(fill_generic(&mut aligned), 3);
+    assert_eq!(FixedCapacityVec::pop(&mut aligned), Some(7));
+
+    let mut saturating = crate::policy::PolicyVec::<i32, 2, crate::policy::Saturate>::new();
+    assert_eq!(fill_generic(&mut saturating), 2);
+    // push follows the policy so is discarded rather than panicking
+    FixedCapacityVec::push(&mut saturating, 8);
+    assert_eq!(FixedCapacityVec::as_slice(&saturating), &[7, 7]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn fixed_capacity_vec() {
+    let mut vec = std::vec![1, 2];
+    FixedCapacityVec::push(&mut vec, 3);
+    assert_eq!(FixedCapacityVec::try_push(&mut vec, 4), Ok(()));
+    assert!(!FixedCapacityVec::is_full(&vec));
+    assert_eq!(FixedCapacityVec::as_slice(&vec), &[1, 2, 3, 4]);
+}
+
+#[test]
+fn find_or_push_nums() {
+    let mut nums = init_stack_half_full();
+    *nums.find_or_push(|&i| i == 2, || unreachable!()).unwrap() = 20;
+    assert_eq!(nums.find_or_push(|&i| i == 4, || 4), Ok(&mut 4));
+    assert_eq!(nums.find_or_push(|&i| i == 5, || 5), Ok(&mut 5));
+    assert_eq!(*nums, [1, 20, 3, 4, 5]);
+    assert_eq!(
+        nums.find_or_push(|&i| i == 6, || unreachable!()),
+        Err(CapacityError::new(()))
+    );
+    assert_eq!(
+        nums.find_or_push(|&i| i == 3, || unreachable!()),
+        Ok(&mut 3)
+    );
+}
+
+#[test]
+fn reserve_nums() {
+    let mut nums = init_stack_half_full();
+    assert_eq!(nums.remaining_capacity(), 2);
+    assert_eq!(nums.try_reserve(2), Ok(()));
+    assert_eq!(nums.try_reserve(3), Err(CapacityError::new(())));
+    nums.extend([4, 5]);
+    assert_eq!(nums.remaining_capacity(), 0);
+    assert_eq!(nums.try_reserve(0), Ok(()));
+}
+
+#[test]
+fn retain_enumerated_nums() {
+    let mut nums = ArrayVec::<_, 10>::new();
+    nums.extend(10..20);
+    nums.retain_enumerated(|i, num| {
+        *num += 1;
+        i % 3 == 0
+    });
+    assert_eq!(*nums, [11, 14, 17, 20]);
+}
+
+#[test]
+fn dedup_nums() {
+    let mut nums = ArrayVec::<_, 10>::new();
+    nums.extend([1, 1, 2, 3, 3, 3, 1, 4, 4, 4]);
+    nums.dedup();
+    assert_eq!(*nums, [1, 2, 3, 1, 4]);
+    nums.dedup_by_key(|i| *i / 2);
+    assert_eq!(*nums, [1, 2, 1, 4]);
+}
+
+#[test]
+fn sort_dedup_nums() {
+    let mut empty = ArrayVec::<i32, 4>::new();
+    empty.sort_unstable_dedup();
+    assert!(empty.is_empty());
+    assert!(!empty.contains_duplicates());
+    let mut nums = ArrayVec::<_, 6>::new();
+    nums.extend([5, 1, 5, 3, 1, 1]);
+    assert!(nums.is_full());
+    assert!(nums.contains_duplicates());
+    nums.sort_unstable_dedup();
+    assert_eq!(*nums, [1, 3, 5]);
+    assert!(!nums.contains_duplicates());
+    assert!(!init_stack_full().contains_duplicates());
+}
+
+#[test]
+fn partition_nums() {
+    let (odd, even) = init_stack_full().partition(|i| i % 2 == 1);
+    assert_eq!(*odd, [1, 3, 5]);
+    assert_eq!(*even, [2, 4]);
+    let (all, none) = init_stack_full().partition(|_| true);
+    assert!(all.is_full());
+    assert!(none.is_empty());
+}
+
+#[test]
+fn front_nums() {
+    let mut queue = ArrayVec::<_, 3>::new();
+    queue.push_front(1);
+    queue.push_front(2);
+    assert_eq!(queue.try_push_front(3), Ok(()));
+    assert_eq!(queue.try_push_front(4), Err(CapacityError::new(4)));
+    assert_eq!(*queue, [3, 2, 1]);
+    assert_eq!(queue.pop_front(), Some(3));
+    assert_eq!(queue.pop_front(), Some(2));
+    assert_eq!(queue.pop_front(), Some(1));
+    assert_eq!(queue.pop_front(), None);
+}
+
+#[test]
+fn cells_nums() {
+    let mut nums = init_stack_half_full();
+    let cells = nums.as_cells();
+    for (a, b) in cells.iter().zip(&cells[1..]) {
+        b.set(a.get() + b.get());
+    }
+    assert_eq!(*nums, [1, 3, 6]);
+}
+
+#[test]
+fn array_windows_nums() {
+    let nums = init_stack_full();
+    let sums = nums
+        .array_windows::<3>()
+        .map(|[a, b, c]| a + b + c)
+        .collect::<ArrayVec<_, 3>>();
+    assert_eq!(*sums, [6, 9, 12]);
+    assert_eq!(nums.array_windows::<6>().len(), 0);
+}
+
+#[test]
+fn array_chunks_mut_nums() {
+    let mut nums = init_stack_full();
+    nums.array_chunks_mut::<2>()
+        .for_each(|pair| pair.swap(0, 1));
+    assert_eq!(*nums, [2, 1, 4, 3, 5]);
+}
+
+fn push_record(nums: &mut ArrayVec<i32, 5>, record: &[i32]) -> Result<(), CapacityError<i32>> {
+    let mut tx = nums.transaction();
+    for &num in record {
+        tx.try_push(num)?;
+    }
+    assert_eq!(tx.pushed(), record);
+    tx.commit();
+    Ok(())
+}
+
+#[test]
+fn transaction_nums() {
+    let mut nums = ArrayVec::new();
+    assert_eq!(push_record(&mut nums, &[1, 2]), Ok(()));
+    assert_eq!(
+        push_record(&mut nums, &[3, 4, 5, 6]),
+        Err(CapacityError::new(6))
+    );
+    assert_eq!(*nums, [1, 2]);
+    assert_eq!(push_record(&mut nums, &[3, 4, 5]), Ok(()));
+    assert!(nums.is_full());
+
+    let drops = Cell::new(0);
+    let mut vec = ArrayVec::<_, 4>::new();
+    let mut tx = vec.transaction();
+    tx.push(PanicDrop {
+        drops: &drops,
+        panics: false,
+    });
+    assert_eq!(tx.len(), 1);
+    drop(tx);
+    assert_eq!(drops.get(), 1);
+    assert!(vec.is_empty());
+}
+
+#[test]
+fn try_into_array_nums() {
+    let mut nums = init_stack_half_full().try_into_array().unwrap_err();
+    nums.extend([4, 5]);
+    assert_eq!(nums.try_into_array().unwrap(), [1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn retain_drain_nums() {
+    let mut timers = ArrayVec::<_, 10>::new();
+    timers.extend([5, 1, 7, 2, 9, 3]);
+    let fired = timers.retain_drain(|&t| t > 4).collect::<ArrayVec<_, 10>>();
+    assert_eq!(*fired, [1, 2, 3]);
+    assert_eq!(*timers, [5, 7, 9]);
+
+    let mut nums = init_stack_full();
+    let mut evens = nums.retain_drain(|i| i % 2 == 1);
+    assert_eq!(evens.next(), Some(2));
+    drop(evens);
+    assert_eq!(*nums, [1, 3, 4, 5]);
+}
+
+#[test]
+fn hex_codec() {
+    use crate::codec::DecodeError;
+    let bytes = ArrayVec::<u8, 4>::from_hex("DEadbe0f").unwrap();
+    assert_eq!(*bytes, [0xde, 0xad, 0xbe, 0x0f]);
+    let mut hex = ArrayVec::<u8, 8>::new();
+    bytes.encode_hex_into(&mut hex).unwrap();
+    assert_eq!(*hex, *b"deadbe0f");
+    assert!(bytes.encode_hex_into(&mut hex).is_err());
+    assert_eq!(hex.len(), 8);
+    assert_eq!(
+        ArrayVec::<u8, 4>::from_hex("abc").unwrap_err(),
+        DecodeError::InvalidLength
+    );
+    assert_eq!(
+        ArrayVec::<u8, 4>::from_hex("0g").unwrap_err(),
+        DecodeError::InvalidCharacter(1)
+    );
+    assert_eq!(
+        ArrayVec::<u8, 1>::from_hex("0000").unwrap_err(),
+        DecodeError::Capacity(CapacityError::new(()))
+    );
+}
+
+#[test]
+fn base64_codec() {
+    use crate::codec::DecodeError;
+    for (raw, encoded) in [
+        (&b""[..], &b""[..]),
+        (b"f", b"Zg=="),
+        (b"fo", b"Zm8="),
+        (b"foo", b"Zm9v"),
+        (b"foob", b"Zm9vYg=="),
+        (b"fooba", b"Zm9vYmE="),
+        (b"foobar", b"Zm9vYmFy"),
+    ] {
+        let decoded =
+            ArrayVec::<u8, 6>::from_base64(core::str::from_utf8(encoded).unwrap()).unwrap();
+        assert_eq!(*decoded, *raw);
+        let mut out = ArrayVec::<u8, 8>::new();
+        decoded.encode_base64_into(&mut out).unwrap();
+        assert_eq!(*out, *encoded);
+    }
+    assert_eq!(
+        ArrayVec::<u8, 6>::from_base64("Zg=").unwrap_err(),
+        DecodeError::InvalidLength
+    );
+    assert_eq!(
+        ArrayVec::<u8, 6>::from_base64("Z===").unwrap_err(),
+        DecodeError::InvalidCharacter(1)
+    );
+    assert_eq!(
+        ArrayVec::<u8, 6>::from_base64("Z=g=").unwrap_err(),
+        DecodeError::InvalidCharacter(1)
+    );
+    assert_eq!(
+        ArrayVec::<u8, 6>::from_base64("Zm9v!A==").unwrap_err(),
+        DecodeError::InvalidCharacter(4)
+    );
+    // non-zero bits cut off by the padding
+    assert_eq!(
+        ArrayVec::<u8, 6>::from_base64("AB==").unwrap_err(),
+        DecodeError::InvalidCharacter(1)
+    );
+    assert_eq!(
+        ArrayVec::<u8, 6>::from_base64("Zm9vZm9=").unwrap_err(),
+        DecodeError::InvalidCharacter(6)
+    );
+    assert_eq!(*ArrayVec::<u8, 6>::from_base64("AA==").unwrap(), [0]);
+    assert!(ArrayVec::<u8, 2>::from_base64("Zm9v").is_err());
+}
+
+#[test]
+fn gap_buffer_editing() {
+    let mut line = ArrayGapBuffer::<char, 8>::new();
+    "helo".chars().for_each(|c| line.insert(c));
+    assert!(line.move_left());
+    line.insert('l');
+    assert_eq!(line.before(), &['h', 'e', 'l', 'l']);
+    assert_eq!(line.after(), &['o']);
+    line.set_cursor(0);
+    assert!(!line.move_left());
+    assert_eq!(line.delete_after(), Some('h'));
+    line.insert('j');
+    line.set_cursor(5);
+    assert!(!line.move_right());
+    assert_eq!(line.delete_before(), Some('o'));
+    assert_eq!(line.delete_after(), None);
+    assert!(line.iter().eq(&['j', 'e', 'l', 'l']));
+    "1234".chars().for_each(|c| line.insert(c));
+    assert!(line.is_full());
+    assert_eq!(line.try_insert('!'), Err(CapacityError::new('!')));
+    line.set_cursor(2);
+    line.set_cursor(7);
+    line.set_cursor(3);
+    assert!(line.iter().eq(&['j', 'e', 'l', 'l', '1', '2', '3', '4']));
+}
+
+#[test]
+fn gap_buffer_drops() {
+    let drops = Cell::new(0);
+    let mut buf = ArrayGapBuffer::<_, 4>::new();
+    (0..4).for_each(|_| {
+        buf.insert(PanicDrop {
+            drops: &drops,
+            panics: false,
+        });
+    });
+    buf.set_cursor(1);
+    drop(buf.delete_after());
+    assert_eq!(drops.get(), 1);
+    drop(buf);
+    assert_eq!(drops.get(), 4);
+
+    // a panic dropping the first half still drops the second
+    let drops = Cell::new(0);
+    let mut buf = ArrayGapBuffer::<_, 4>::new();
+    init_panic_drops(&drops, 0)
+        .into_iter()
+        .take(4)
+        .for_each(|d| buf.insert(d));
+    assert_eq!(drops.get(), 1);
+    buf.set_cursor(2);
+    assert!(catch_unwind(AssertUnwindSafe(|| buf.clear())).is_err());
+    assert_eq!(drops.get(), 5);
+    assert!(buf.is_empty());
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Event {
+    time: u8,
+    id: u8,
+}
+
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.time == other.time
+    }
+}
+
+impl Eq for Event {}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.time.cmp(&other.time)
+    }
+}
+
+fn events() -> ArrayVec<Event, 16> {
+    [3, 1, 2, 1, 3, 0, 2, 1, 0, 3, 2]
+        .into_iter()
+        .enumerate()
+        .map(|(id, time)| Event {
+            time,
+            id: u8::try_from(id).unwrap(),
+        })
+        .collect()
+}
+
+fn assert_stably_sorted(events: &[Event]) {
+    for [a, b] in events.windows(2).map(|w| [w[0], w[1]]) {
+        assert!(a.time < b.time || (a.time == b.time && a.id < b.id));
+    }
+}
+
+#[test]
+fn sort_stable_events() {
+    let mut in_spare = events();
+    in_spare.sort_stable();
+    assert_stably_sorted(&in_spare);
+    assert_eq!(in_spare.len(), 11);
+
+    let mut full = events().try_shrink::<11>().unwrap();
+    full.sort_stable();
+    assert_stably_sorted(&full);
+
+    let mut scratch = ArrayVec::<Event, 5>::new();
+    let mut with_scratch = events();
+    with_scratch.sort_stable_with_scratch(scratch.spare_capacity_mut());
+    assert_stably_sorted(&with_scratch);
+
+    let mut empty = ArrayVec::<Event, 0>::new();
+    empty.sort_stable();
+}
+
+#[test]
+fn sort_stable_panicking_cmp() {
+    let cmps = Cell::new(0);
+    let mut bombs = [5, 3, 8, 1, 9, 2, 7, 4, 6, 0]
+        .into_iter()
+        .map(|value| PanicCmp {
+            value,
+            cmps: &cmps,
+            panic_at: 10,
+        })
+        .collect::<ArrayVec<_, 10>>();
+    assert!(catch_unwind(AssertUnwindSafe(|| bombs.sort_stable())).is_err());
+    let mut seen = bombs.iter().map(|b| b.value).collect::<ArrayVec<_, 10>>();
+    seen.sort_unstable();
+    assert_eq!(*seen, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+}
+
+#[test]
+fn aligned_storage() {
+    let mut bytes = AlignedArrayVec::<u8, 100, Align64>::new();
+    bytes.extend_from_slice(&[1, 2, 3]);
+    assert_eq!(bytes.as_ptr() as usize % 64, 0);
+    assert_eq!(core::mem::align_of_val(&bytes), 64);
+    assert_eq!(*bytes.into_inner(), [1, 2, 3]);
+}
+
+#[test]
+fn le_be_bytes_codec() {
+    use crate::codec::DecodeError;
+    let regs = ArrayVec::<u16, 4>::from_iter([0x1234, 0xabcd]);
+    let mut out = ArrayVec::<u8, 8>::new();
+    regs.to_le_bytes_into(&mut out).unwrap();
+    regs.to_be_bytes_into(&mut out).unwrap();
+    assert_eq!(*out, [0x34, 0x12, 0xcd, 0xab, 0x12, 0x34, 0xab, 0xcd]);
+    assert!(regs.to_le_bytes_into(&mut out).is_err());
+    assert_eq!(out.len(), 8);
+
+    let le = ArrayVec::<u16, 4>::from_le_bytes(&out[..4]).unwrap();
+    let be = ArrayVec::<u16, 4>::from_be_bytes(&out[4..]).unwrap();
+    assert_eq!(*le, *regs);
+    assert_eq!(*be, *regs);
+
+    let floats = ArrayVec::<f32, 2>::from_le_bytes(&1.5f32.to_le_bytes()).unwrap();
+    assert_eq!(*floats, [1.5]);
+    assert_eq!(
+        ArrayVec::<u32, 2>::from_le_bytes(&[0; 6]).unwrap_err(),
+        DecodeError::InvalidLength
+    );
+    assert!(matches!(
+        ArrayVec::<u32, 1>::from_le_bytes(&[0; 8]),
+        Err(DecodeError::Capacity(_))
+    ));
+}
+
+#[test]
+fn invariants_after_panics() {
+    let drops = Cell::new(0);
+    let mut nums = init_panic_drops(&drops, 2);
+    assert!(catch_unwind(AssertUnwindSafe(|| nums.retain(|d| !d.panics))).is_err());
+    nums.assert_invariants();
+    assert_eq!(nums.len(), 4);
+
+    let mut bytes = ArrayVec::<u8, 4>::from_iter([1, 2, 3]);
+    bytes.truncate(1);
+    bytes.assert_invariants();
+    #[cfg(feature = "debug-invariants")]
+    // SAFETY: poisoning initialised the truncated bytes
+    assert_eq!(unsafe { bytes.spare_capacity_mut()[0].assume_init() }, 0xa5);
+}
+
+/// The bytes of every slot past the length, which must all have been poisoned
+#[cfg(feature = "debug-invariants")]
+fn spare_bytes<const C: usize>(vec: &mut ArrayVec<u8, C>) -> std::vec::Vec<u8> {
+    vec.spare_capacity_mut()
+        .iter()
+        // SAFETY: the caller only uses this on vectors that have been full
+        .map(|b| unsafe { b.assume_init() })
+        .collect()
+}
+
+#[test]
+#[cfg(feature = "debug-invariants")]
+fn freed_slots_poisoned() {
+    let full = || ArrayVec::<u8, 4>::from_iter([1, 2, 3, 4]);
+    let mut bytes = full();
+    bytes.pop();
+    bytes.remove(0);
+    bytes.swap_remove(0);
+    assert_eq!(*bytes, [3]);
+    assert_eq!(spare_bytes(&mut bytes), [0xa5; 3]);
+
+    let mut bytes = full();
+    let mut other = ArrayVec::<u8, 8>::new();
+    other.append(&mut bytes);
+    assert_eq!(spare_bytes(&mut bytes), [0xa5; 4]);
+
+    let (mut head, tail) = full().split_into(1);
+    assert_eq!(*tail, [2, 3, 4]);
+    assert_eq!(spare_bytes(&mut head), [0xa5; 3]);
+
+    // a panicking Drop still leaves every truncated slot poisoned
+    let drops = Cell::new(0);
+    let mut nums = init_panic_drops(&drops, 2);
+    assert!(catch_unwind(AssertUnwindSafe(|| nums.truncate(1))).is_err());
+    assert_eq!(drops.get(), 4);
+    let spare = nums.spare_capacity_mut();
+    for slot in &spare[..4] {
+        // SAFETY: poisoning initialised the bytes of every truncated slot
+        let bytes: [u8; size_of::<PanicDrop>()] = unsafe { core::mem::transmute_copy(slot) };
+        assert!(bytes.iter().all(|&b| b == 0xa5));
+    }
+}
+
+#[test]
+fn overflow_policies() {
+    use crate::policy::{Overwrite, Panic, PolicyVec, Saturate};
+    let mut saturating = PolicyVec::<u8, 3, Saturate>::new();
+    saturating.extend(0..3);
+    assert_eq!(saturating.push(3), Some(3));
+    assert_eq!(**saturating, [0, 1, 2]);
+
+    let mut overwriting = PolicyVec::<u8, 3, Overwrite>::new();
+    overwriting.extend(0..3);
+    assert_eq!(overwriting.push(3), Some(0));
+    assert_eq!(**overwriting, [1, 2, 3]);
+    overwriting.as_mut_slice()[0] = 4;
+    assert_eq!(overwriting.remove(0), 4);
+    assert_eq!(overwriting.pop(), Some(3));
+    overwriting.extend(5..8);
+    assert_eq!(**overwriting, [5, 6, 7]);
+    overwriting.truncate(1);
+    assert_eq!(**overwriting, [5]);
+    overwriting.clear();
+    assert!(overwriting.is_empty());
+
+    let mut none = PolicyVec::<u8, 0, Overwrite>::new();
+    assert_eq!(none.push(0), Some(0));
+
+    let mut panicking = PolicyVec::<u8, 1, Panic>::new();
+    assert_eq!(panicking.push(0), None);
+    assert!(catch_unwind(AssertUnwindSafe(|| panicking.push(1))).is_err());
+}
+
+#[test]
+fn concat_operators() {
+    let a = ArrayVec::<u8, 6>::from_iter([1, 2]);
+    let b = ArrayVec::<u8, 2, u8>::from_iter([3, 4]);
+    let mut sum = a + b + &[5][..];
+    assert_eq!(*sum, [1, 2, 3, 4, 5]);
+    sum += &[6][..];
+    assert_eq!(*sum, [1, 2, 3, 4, 5, 6]);
+    assert!(catch_unwind(AssertUnwindSafe(|| sum.clone() + &[7][..])).is_err());
+
+    let mut other = ArrayVec::<u8, 2>::from_iter([7, 8]);
+    assert!(sum.try_append(&mut other).is_err());
+    assert_eq!(other.len(), 2);
+    assert!(sum.try_extend_from_slice(&[7]).is_err());
+    assert_eq!(sum.len(), 6);
+    sum.truncate(4);
+    sum.append(&mut other);
+    assert_eq!(*sum, [1, 2, 3, 4, 7, 8]);
+    assert!(other.is_empty());
+}
+
+#[test]
+fn truncate_front_drops_oldest() {
+    let mut nums = init_stack_full();
+    nums.truncate_front(2);
+    assert_eq!(*nums, [4, 5]);
+    nums.truncate_front(5);
+    assert_eq!(*nums, [4, 5]);
+    nums.truncate_front(2);
+    assert_eq!(*nums, [4, 5]);
+
+    let drops = Cell::new(0);
+    let mut panics = init_panic_drops(&drops, 1);
+    assert!(catch_unwind(AssertUnwindSafe(|| panics.keep_last(2))).is_err());
+    assert_eq!(panics.len(), 3);
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn dedup_with_count_runs() {
+    let states = "aaabccaa".chars().collect::<ArrayVec<char, 8>>();
+    let runs = states.dedup_with_count();
+    assert_eq!(*runs, [('a', 3), ('b', 1), ('c', 2), ('a', 2)]);
+    assert!(ArrayVec::<u8, 0>::new().dedup_with_count().is_empty());
+}
+
+#[test]
+#[cfg(feature = "allocator_api")]
+fn fallback_vec_spills() {
+    let mut vec = crate::FallbackVec::<u8, 2, _>::new_in(std::alloc::Global);
+    vec.extend([1, 2]);
+    assert!(!vec.is_spilled());
+    vec.push(3);
+    assert!(vec.is_spilled());
+    assert_eq!(*vec, [1, 2, 3]);
+    assert_eq!(vec.pop(), Some(3));
+    assert_eq!(std::format!("{vec:?}"), "[1, 2]");
+    assert_eq!(FixedCapacityVec::try_push(&mut vec, 4), Ok(()));
+    assert!(!FixedCapacityVec::is_full(&vec));
+}
+
+#[test]
+fn unzip_pairs() {
+    let pairs = ArrayVec::<(u8, char), 4>::from_iter([(1, 'a'), (2, 'b'), (3, 'c')]);
+    let (nums, chars) = pairs.unzip();
+    assert_eq!(*nums, [1, 2, 3]);
+    assert_eq!(*chars, ['a', 'b', 'c']);
+}
+
+#[test]
+fn zip_with_channels() {
+    let left = ArrayVec::<i16, 4>::from_iter([100, -50, 25]);
+    let right = ArrayVec::<i16, 4>::from_iter([10, 20]);
+    let mixed = left.zip_with(right, |l, r| l / 2 + r / 2);
+    assert_eq!(*mixed, [55, -15]);
+
+    let drops = Cell::new(0);
+    let none = ArrayVec::<PanicDrop<'_>, 5>::new();
+    let zipped = init_panic_drops(&drops, usize::MAX).zip_with(none, |a, _| a);
+    assert!(zipped.is_empty());
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn pop_iter_reuses_buffer() {
+    let mut nums = init_stack_full();
+    assert_eq!(nums.pop_iter().take(2).sum::<i32>(), 9);
+    assert_eq!(*nums, [1, 2, 3]);
+    assert!(nums.pop_iter().eq([3, 2, 1]));
+    assert!(nums.is_empty());
+    nums.push(6);
+    assert_eq!(*nums, [6]);
+}
+
+#[test]
+fn equal_range_timestamps() {
+    let events =
+        ArrayVec::<(u8, char), 8>::from_iter([(1, 'a'), (3, 'b'), (3, 'c'), (3, 'd'), (7, 'e')]);
+    assert_eq!(events.equal_range_by_key(&3, |e| e.0), 1..4);
+    assert_eq!(events.equal_range_by_key(&1, |e| e.0), 0..1);
+    assert_eq!(events.equal_range_by_key(&5, |e| e.0), 4..4);
+    assert_eq!(events.equal_range_by_key(&9, |e| e.0), 5..5);
+}
+
+#[test]
+fn try_extend_until_full() {
+    fn fill<V: TryExtend<u8>>(v: &mut V) -> Result<(), CapacityError<u8>> {
+        v.try_extend(1..=4)
+    }
+    let mut nums = ArrayVec::<u8, 3>::new();
+    assert_eq!(fill(&mut nums).unwrap_err().element(), 4);
+    assert_eq!(*nums, [1, 2, 3]);
+
+    let mut refs = ArrayVec::<u8, 1>::new();
+    assert_eq!(refs.try_extend(&[5, 6]).unwrap_err().element(), &6);
+    assert_eq!(*refs, [5]);
+
+    let mut aligned = AlignedArrayVec::<u8, 2, Align64>::new();
+    assert_eq!(fill(&mut aligned).unwrap_err().element(), 3);
+    assert_eq!(**aligned, [1, 2]);
+
+    // stops at capacity instead of following the policy
+    let mut overwriting = crate::policy::PolicyVec::<u8, 2, crate::policy::Overwrite>::new();
+    assert_eq!(fill(&mut overwriting).unwrap_err().element(), 3);
+    assert_eq!(**overwriting, [1, 2]);
+
+    let mut gap = ArrayGapBuffer::<u8, 5>::new();
+    gap.insert(9);
+    gap.move_left();
+    assert_eq!(fill(&mut gap), Ok(()));
+    assert_eq!(gap.before(), [1, 2, 3, 4]);
+    assert_eq!(gap.after(), [9]);
+    assert_eq!(gap.try_extend([5]).unwrap_err().element(), 5);
+
+    #[cfg(feature = "alloc")]
+    {
+        let mut vec = std::vec::Vec::new();
+        assert_eq!(fill(&mut vec), Ok(()));
+        assert_eq!(vec, [1, 2, 3, 4]);
+    }
+
+    #[cfg(feature = "allocator_api")]
+    {
+        let mut fallback = crate::FallbackVec::<u8, 2, _>::new_in(std::alloc::Global);
+        assert_eq!(fill(&mut fallback), Ok(()));
+        assert_eq!(*fallback, [1, 2, 3, 4]);
+    }
+}
+
+#[test]
+fn byte_queue_grants() {
+    let mut queue = ByteQueue::<8>::new();
+    let (mut producer, mut consumer) = queue.split();
+    let mut grant = producer.grant(6).unwrap();
+    grant[..4].copy_from_slice(b"abcd");
+    grant.commit(4);
+    let read = consumer.read();
+    assert_eq!(*read, *b"abcd");
+    read.release(3);
+
+    let mut grant = producer.grant(4).unwrap();
+    grant.copy_from_slice(b"efgh");
+    grant.commit(4);
+    // no room left at the end and 3 bytes would run into the reader
+    assert!(producer.grant(3).is_err());
+    let mut grant = producer.grant(2).unwrap();
+    grant.copy_from_slice(b"ij");
+    // both halves can hold a grant at once
+    let read = consumer.read();
+    assert_eq!(*read, *b"defgh");
+    grant.commit(2);
+    read.release(5);
+    assert_eq!(consumer.len(), 2);
+    assert!(producer.grant(1).is_ok());
+
+    let read = consumer.read();
+    assert_eq!(*read, *b"ij");
+    read.release(2);
+    assert!(consumer.is_empty());
+    assert!(consumer.read().is_empty());
+    assert!(producer.grant(8).is_err());
+    assert_eq!(producer.grant(6).unwrap().len(), 6);
+
+    queue.clear();
+    let (mut producer, _) = queue.split();
+    assert_eq!(producer.grant(8).unwrap().len(), 8);
+}
+
+#[test]
+fn byte_queue_threads() {
+    let mut queue = ByteQueue::<16>::new();
+    let (mut producer, mut consumer) = queue.split();
+    std::thread::scope(|s| {
+        s.spawn(move || {
+            let mut next = 0u8;
+            while next < 200 {
+                let n = usize::from(next % 5 + 1);
+                if let Ok(mut grant) = producer.grant(n) {
+                    for byte in grant.iter_mut() {
+                        *byte = next;
+                        next = next.wrapping_add(1);
+                    }
+                    grant.commit(n);
+                }
+            }
+        });
+        let mut expected = 0u8;
+        while expected < 200 {
+            let read = consumer.read();
+            for &byte in read.iter() {
+                assert_eq!(byte, expected);
+                expected = expected.wrapping_add(1);
+            }
+            let len = read.len();
+            read.release(len);
+        }
+    });
+}
+
+#[test]
+fn try_extend_exact_rolls_back() {
+    let mut nums = init_stack_half_full();
+    let len = nums.len();
+    assert_eq!(
+        nums.try_extend_exact(4..5, 2),
+        Err(ExtendExactError::TooFew(1))
+    );
+    assert_eq!(nums.len(), len);
+    let mut too_many = 4..8;
+    assert_eq!(
+        nums.try_extend_exact(too_many.by_ref(), 2),
+        Err(ExtendExactError::TooMany)
+    );
+    assert_eq!(nums.len(), len);
+    // the item checked for being one too many is used up
+    assert_eq!(too_many.next(), Some(7));
+    let panicking = (4..6).map(|i| {
+        if i == 5 {
+            panic!("iterator panicked")
+        } else {
+            i
+        }
+    });
+    assert!(catch_unwind(AssertUnwindSafe(|| nums.try_extend_exact(panicking, 2))).is_err());
+    assert_eq!(*nums, [1, 2, 3]);
+    assert!(matches!(
+        nums.try_extend_exact(0.., 10),
+        Err(ExtendExactError::Capacity(_))
+    ));
+    assert_eq!(nums.try_extend_exact(4..6, 2), Ok(()));
+    assert_eq!(nums.len(), len + 2);
+}
+
+#[test]
+fn retain_map_normalizes() {
+    let mut nums = ArrayVec::<i32, 8>::from_iter([3, -1, 8, 0, -5, 2]);
+    nums.retain_map(|i| (i != 0).then_some(i.abs()));
+    assert_eq!(*nums, [3, 1, 8, 5, 2]);
+
+    let drops = Cell::new(0);
+    let mut vec = init_panic_drops(&drops, usize::MAX);
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        vec.retain_map(|d| {
+            assert!(drops.get() < 2, "predicate panicked");
+            drop(d);
+            None
+        });
+    }));
+    assert!(res.is_err());
+    // the element passed to the panicking closure is dropped by the unwind
+    assert_eq!(drops.get(), 3);
+    assert_eq!(vec.len(), 2);
+}
+
+#[test]
+fn swap_contents_double_buffer() {
+    let mut front = init_stack_half_full();
+    let mut back = ArrayVec::<i32, 8, u8>::from_iter([7, 8, 9, 10]);
+    front.swap_contents(&mut back).unwrap();
+    assert_eq!(*front, [7, 8, 9, 10]);
+    assert_eq!(*back, [1, 2, 3]);
+    back.extend(4..7);
+    assert!(front.swap_contents(&mut back).is_err());
+    assert_eq!(*front, [7, 8, 9, 10]);
+    assert_eq!(back.len(), 6);
+
+    let drops = Cell::new(0);
+    let mut panics = init_panic_drops(&drops, usize::MAX);
+    let mut empty = ArrayVec::<PanicDrop<'_>, 5>::new();
+    panics.swap_contents(&mut empty).unwrap();
+    assert!(panics.is_empty());
+    drop(empty);
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn grid_cells() {
+    let mut grid = ArrayGrid::<u8, 3, 2>::new();
+    assert!(grid.push_row([1, 2, 3]).is_ok());
+    assert_eq!(grid.height(), 1);
+    assert_eq!(grid.get(1, 0), Some(&2));
+    assert_eq!(grid.get(0, 1), None);
+    grid.fill(0);
+    assert!(grid.is_complete());
+    assert!(grid.push_row([0; 3]).is_err());
+
+    let mut grid = ArrayGrid::<usize, 3, 2>::from_fn(|x, y| y * 3 + x);
+    assert!(grid.column(1).eq(&[1, 4]));
+    grid.swap((0, 0), (2, 1));
+    assert_eq!(grid.row(0), Some(&[5, 1, 2]));
+    assert_eq!(grid.row(1), Some(&[3, 4, 0]));
+    *grid.get_mut(1, 1).unwrap() = 9;
+    assert_eq!(std::format!("{grid:?}"), "[[5, 1, 2], [3, 9, 0]]");
+}
+
+#[test]
+fn heap_view_priorities() {
+    let mut timers = ArrayVec::<u8, 8>::from_iter([3, 9, 1, 7, 5]);
+    let mut heap = BinaryHeapView::new(&mut timers);
+    assert_eq!(heap.peek(), Some(&9));
+    heap.push(8);
+    assert_eq!(heap.pop(), Some(9));
+    assert_eq!(heap.pop(), Some(8));
+    heap.into_sorted();
+    assert_eq!(*timers, [1, 3, 5, 7]);
+
+    let mut full = init_stack_full();
+    let mut heap = BinaryHeapView::new(&mut full);
+    assert_eq!(heap.try_push(6).unwrap_err().element(), 6);
+    assert!(core::iter::from_fn(|| heap.pop()).eq([5, 4, 3, 2, 1]));
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn sort_stable_zst() {
+    let mut units = ArrayVec::<(), 4>::from_iter([(), ()]);
+    units.sort_stable();
+    assert_eq!(units.len(), 2);
+    units.sort_stable_with_scratch(ArrayVec::<(), 1>::new().spare_capacity_mut());
+    assert_eq!(units.len(), 2);
+}
+
+#[test]
+fn sort_stable_with_scratch_panicking_cmp() {
+    let cmps = Cell::new(0);
+    let mut bombs = [4, 9, 1, 7, 3, 8, 0, 6, 2, 5]
+        .into_iter()
+        .map(|value| PanicCmp {
+            value,
+            cmps: &cmps,
+            panic_at: 12,
+        })
+        .collect::<ArrayVec<_, 10>>();
+    let mut scratch = ArrayVec::<PanicCmp<'_>, 5>::new();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        bombs.sort_stable_with_scratch(scratch.spare_capacity_mut());
+    }));
+    assert!(res.is_err());
+    let mut seen = bombs.iter().map(|b| b.value).collect::<ArrayVec<_, 10>>();
+    seen.sort_unstable();
+    assert_eq!(*seen, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+}
@@ -0,0 +1,57 @@
+use core::{mem, ops};
+
+use crate::{ArrayVec, CapacityError, LenType};
+
+/// Pushes made through this are rolled back when it's dropped
+/// without calling [`Transaction::commit`]
+///
+/// Created by [`ArrayVec::transaction`]
+pub struct Transaction<'a, T, const C: usize, L: LenType = usize> {
+    vec: &'a mut ArrayVec<T, C, L>,
+    start: usize,
+}
+
+impl<'a, T, const C: usize, L: LenType> Transaction<'a, T, C, L> {
+    pub(crate) fn new(vec: &'a mut ArrayVec<T, C, L>) -> Self {
+        let start = vec.len();
+        Self { vec, start }
+    }
+
+    /// Appends an item to the end of the vector
+    /// # Panics
+    /// If the vector is full, rolling back the transaction
+    pub fn push(&mut self, item: T) {
+        self.vec.push(item);
+    }
+
+    /// Appends an item to the end of the vector
+    /// # Errors
+    /// Gives back `item` if the vector is full
+    pub fn try_push(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.vec.try_push(item)
+    }
+
+    /// The elements pushed so far in this transaction
+    #[must_use]
+    pub fn pushed(&self) -> &[T] {
+        &self.vec[self.start..]
+    }
+
+    /// Keeps everything pushed
+    pub fn commit(self) {
+        mem::forget(self);
+    }
+}
+
+impl<T, const C: usize, L: LenType> ops::Deref for Transaction<'_, T, C, L> {
+    type Target = ArrayVec<T, C, L>;
+    fn deref(&self) -> &Self::Target {
+        self.vec
+    }
+}
+
+impl<T, const C: usize, L: LenType> Drop for Transaction<'_, T, C, L> {
+    fn drop(&mut self) {
+        self.vec.truncate(self.start);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    aligned::{AlignedArrayVec, Alignment},
+    policy::{OverflowPolicy, PolicyVec},
+    ArrayGapBuffer, ArrayVec, CapacityError, LenType,
+};
+
+/// Fallible [`Extend`], for code generic over bounded containers
+pub trait TryExtend<A> {
+    /// Appends items until the iterator runs out or the container is full,
+    /// keeping whatever was appended
+    /// # Errors
+    /// Gives back the first item that didn't fit
+    fn try_extend<I: IntoIterator<Item = A>>(&mut self, iter: I) -> Result<(), CapacityError<A>>;
+}
+
+impl<T, const C: usize, L: LenType> TryExtend<T> for ArrayVec<T, C, L> {
+    fn try_extend<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), CapacityError<T>> {
+        iter.into_iter().try_for_each(|item| self.try_push(item))
+    }
+}
+
+impl<'a, T, const C: usize, L: LenType> TryExtend<&'a T> for ArrayVec<T, C, L>
+where
+    T: Clone,
+{
+    fn try_extend<I: IntoIterator<Item = &'a T>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), CapacityError<&'a T>> {
+        iter.into_iter().try_for_each(|item| {
+            if self.is_full() {
+                return Err(CapacityError::new(item));
+            }
+            self.push(item.clone());
+            Ok(())
+        })
+    }
+}
+
+impl<T, const C: usize, A: Alignment, L: LenType> TryExtend<T> for AlignedArrayVec<T, C, A, L> {
+    fn try_extend<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), CapacityError<T>> {
+        ArrayVec::try_extend(self, iter)
+    }
+}
+
+/// Stops once full whatever the policy, like `FixedCapacityVec::try_push`
+impl<T, const C: usize, P: OverflowPolicy, L: LenType> TryExtend<T> for PolicyVec<T, C, P, L> {
+    fn try_extend<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), CapacityError<T>> {
+        iter.into_iter().try_for_each(|item| {
+            if self.is_full() {
+                return Err(CapacityError::new(item));
+            }
+            self.push(item);
+            Ok(())
+        })
+    }
+}
+
+/// Inserts at the cursor, leaving it after the new elements
+impl<T, const C: usize> TryExtend<T> for ArrayGapBuffer<T, C> {
+    fn try_extend<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), CapacityError<T>> {
+        iter.into_iter().try_for_each(|item| self.try_insert(item))
+    }
+}
+
+/// Unbounded so never fails
+#[cfg(feature = "alloc")]
+impl<T> TryExtend<T> for alloc::vec::Vec<T> {
+    fn try_extend<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), CapacityError<T>> {
+        self.extend(iter);
+        Ok(())
+    }
+}
+
+/// Spills rather than filling up so never fails
+#[cfg(feature = "allocator_api")]
+impl<T, const C: usize, A: core::alloc::Allocator> TryExtend<T> for crate::FallbackVec<T, C, A> {
+    fn try_extend<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), CapacityError<T>> {
+        self.extend(iter);
+        Ok(())
+    }
+}
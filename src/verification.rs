@@ -0,0 +1,105 @@
+//! Kani harnesses for the unsafe core, run with `cargo kani`
+//!
+//! Each harness builds a vector of symbolic length with symbolic elements so
+//! every length up to the capacity and every index is covered
+//!
+//! The crate has no `drain`, runs of elements are removed by
+//! [`ArrayVec::retain_drain`] and [`ArrayVec::truncate_front`] so those have
+//! harnesses instead, `pop_iter` only repeats `pop`
+
+use crate::ArrayVec;
+
+const C: usize = 4;
+
+/// Vector with a symbolic length up to `C` and symbolic elements
+fn any_vec() -> ArrayVec<u8, C> {
+    let len: usize = kani::any();
+    kani::assume(len <= C);
+    let mut vec = ArrayVec::new();
+    for _ in 0..len {
+        vec.push(kani::any());
+    }
+    vec
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn push_pop() {
+    let mut vec = any_vec();
+    let len = vec.len();
+    let item: u8 = kani::any();
+    match vec.try_push(item) {
+        Ok(()) => {
+            assert_eq!(vec.len(), len + 1);
+            assert_eq!(vec.pop(), Some(item));
+        }
+        Err(err) => {
+            assert_eq!(len, C);
+            assert_eq!(err.element(), item);
+        }
+    }
+    assert_eq!(vec.len(), len);
+    vec.assert_invariants();
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn insert_remove() {
+    let mut vec = any_vec();
+    kani::assume(!vec.is_full());
+    let before = vec.clone();
+    let index: usize = kani::any();
+    kani::assume(index <= vec.len());
+    let item: u8 = kani::any();
+    vec.insert(index, item);
+    assert_eq!(vec[index], item);
+    assert_eq!(vec.remove(index), item);
+    assert_eq!(vec.as_slice(), before.as_slice());
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn swap_remove() {
+    let mut vec = any_vec();
+    let before = vec.clone();
+    let index: usize = kani::any();
+    kani::assume(index < vec.len());
+    assert_eq!(vec.swap_remove(index), before[index]);
+    assert_eq!(vec.len(), before.len() - 1);
+    if index < vec.len() {
+        assert_eq!(vec[index], before[before.len() - 1]);
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn truncate() {
+    let mut vec = any_vec();
+    let before = vec.clone();
+    let len: usize = kani::any();
+    vec.truncate(len);
+    assert_eq!(vec.len(), len.min(before.len()));
+    assert_eq!(vec.as_slice(), &before[..vec.len()]);
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn retain_drain() {
+    let mut vec = any_vec();
+    let len = vec.len();
+    let drained = vec.retain_drain(|_| kani::any()).count();
+    assert_eq!(vec.len() + drained, len);
+    vec.assert_invariants();
+}
+
+#[kani::proof]
+#[kani::unwind(6)]
+fn truncate_front() {
+    let mut vec = any_vec();
+    let before = vec.clone();
+    let len: usize = kani::any();
+    vec.truncate_front(len);
+    assert_eq!(vec.len(), len.min(before.len()));
+    assert_eq!(vec.as_slice(), &before[before.len() - vec.len()..]);
+    vec.assert_invariants();
+}